@@ -1 +1,35 @@
-// Additional game-specific HTTP handlers can be added here if needed
+use crate::services::game_manager::GameManager;
+use actix_web::http::header::ContentDisposition;
+use actix_web::{web, HttpResponse};
+use shared::{GameError, ServerMessage};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Downloads a finished game as a [`shared::record`] file.
+pub async fn download_record(
+    game_id: web::Path<Uuid>,
+    game_manager: web::Data<Arc<Mutex<GameManager>>>,
+) -> HttpResponse {
+    let manager = game_manager.lock().unwrap();
+    let record = manager.record(*game_id);
+    let name = manager
+        .get_game(*game_id)
+        .map(|game| game.name.clone())
+        .unwrap_or_default();
+    drop(manager);
+
+    match record {
+        Ok(record) => HttpResponse::Ok()
+            .content_type("text/plain; charset=utf-8")
+            .insert_header(ContentDisposition::attachment(format!("{name}.txt")))
+            .body(record.to_string()),
+        Err(e) => {
+            let mut response = match e {
+                GameError::GameNotFound => HttpResponse::NotFound(),
+                GameError::GameInProgress => HttpResponse::Conflict(),
+                _ => HttpResponse::UnprocessableEntity(),
+            };
+            response.json(ServerMessage::Error(e))
+        }
+    }
+}
@@ -1,10 +1,15 @@
-use crate::services::game_manager::GameManager;
-use actix::{Actor, ActorContext, AsyncContext, Handler, Message, StreamHandler};
+use crate::services::game_manager::{self, GameManager};
+use crate::services::game_room::{Broadcast, GameRoom, Join, Leave, RoomEvent};
+use actix::{
+    Actor, ActorContext, ActorFutureExt, Addr, AsyncContext, Handler, StreamHandler, WrapFuture,
+};
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use actix_web_actors::ws;
-use shared::{ClientMessage, ServerMessage};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use shared::analysis;
+use shared::{
+    ultimate, CellState, ClientMessage, Coordinate, GameError, Move, RawCoordinate, ServerMessage,
+};
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 pub async fn websocket_handler(
@@ -17,17 +22,99 @@ pub async fn websocket_handler(
 }
 
 pub struct GameWebSocket {
+    id: Uuid,
     game_manager: Arc<Mutex<GameManager>>,
     game_id: Option<Uuid>,
     player_id: Option<Uuid>,
+    room: Option<Addr<GameRoom>>,
 }
 
 impl GameWebSocket {
     pub fn new(game_manager: Arc<Mutex<GameManager>>) -> Self {
         Self {
+            id: Uuid::new_v4(),
             game_manager,
             game_id: None,
             player_id: None,
+            room: None,
+        }
+    }
+
+    /// Seats this session in `game_id` as `player_id`, first leaving the game it
+    /// was in.
+    fn enter_game(&mut self, manager: &mut GameManager, game_id: Uuid, player_id: Uuid) {
+        self.leave_game(manager);
+        self.game_id = Some(game_id);
+        self.player_id = Some(player_id);
+    }
+
+    /// Takes this session's player out of its game, as [`GameManager::leave_game`]
+    /// does, and tells the room.
+    fn leave_game(&mut self, manager: &mut GameManager) {
+        if let (Some(game_id), Some(player_id)) = (self.game_id, self.player_id) {
+            if manager.leave_game(game_id, player_id).is_ok() {
+                if let (Some(game), Some(room)) = (manager.get_game(game_id), &self.room) {
+                    room.do_send(Broadcast(ServerMessage::GameState(Box::new(game.clone()))));
+                }
+                actix::spawn(game_manager::play_bots(self.game_manager.clone(), game_id));
+            }
+        }
+    }
+
+    fn join_room(
+        &mut self,
+        room: Addr<GameRoom>,
+        player_name: String,
+        ctx: &mut ws::WebsocketContext<Self>,
+    ) {
+        self.leave_room();
+        room.do_send(Join {
+            session_id: self.id,
+            player_id: self.player_id,
+            player_name,
+            addr: ctx.address().recipient(),
+        });
+        self.room = Some(room);
+    }
+
+    fn leave_room(&mut self) {
+        if let Some(room) = self.room.take() {
+            room.do_send(Leave {
+                session_id: self.id,
+            });
+        }
+    }
+
+    fn play_move(&mut self, mv: Result<Move, GameError>, ctx: &mut ws::WebsocketContext<Self>) {
+        self.update_game(ctx, |manager, game_id, player_id| {
+            mv.and_then(|mv| manager.make_move(game_id, mv, player_id))
+        });
+    }
+
+    /// Runs `update` on the joined game as this session's player, then shows the
+    /// room the new state or tells the session what went wrong.
+    fn update_game(
+        &mut self,
+        ctx: &mut ws::WebsocketContext<Self>,
+        update: impl FnOnce(&mut GameManager, Uuid, Uuid) -> Result<(), GameError>,
+    ) {
+        if let (Some(game_id), Some(player_id)) = (self.game_id, self.player_id) {
+            let mut manager = self.game_manager.lock().unwrap();
+            match update(&mut manager, game_id, player_id) {
+                Ok(()) => {
+                    if let (Some(game), Some(room)) = (manager.get_game(game_id), &self.room) {
+                        room.do_send(Broadcast(ServerMessage::GameState(Box::new(game.clone()))));
+                    }
+                    actix::spawn(game_manager::play_bots(self.game_manager.clone(), game_id));
+                }
+                Err(e) => {
+                    let response = ServerMessage::Error(e);
+                    ctx.text(serde_json::to_string(&response).unwrap());
+                }
+            }
+        } else {
+            let response = ServerMessage::Error(GameError::NotInGame);
+            ctx.text(serde_json::to_string(&response).unwrap());
         }
     }
 
@@ -36,20 +123,32 @@ impl GameWebSocket {
             ClientMessage::CreateGame {
                 game_name,
                 player_name,
+                ruleset,
+                config,
+                seats,
+                opponent,
+                position,
             } => {
-                let mut manager = self.game_manager.try_lock().unwrap();
-                match manager.create_game(game_name, player_name) {
+                let game_manager = self.game_manager.clone();
+                let mut manager = game_manager.lock().unwrap();
+                let created = game_manager::new_game(game_name, ruleset, config, seats, position)
+                    .and_then(|game| manager.create_game(game, player_name.clone(), opponent));
+                match created {
                     Ok((game_id, player_id)) => {
-                        self.game_id = Some(game_id);
-                        self.player_id = Some(player_id);
+                        self.enter_game(&mut manager, game_id, player_id);
 
                         let response = ServerMessage::GameCreated { game_id, player_id };
                         ctx.text(serde_json::to_string(&response).unwrap());
 
+                        let room = manager.room(game_id);
+                        self.join_room(room.clone(), player_name, ctx);
+
                         if let Some(game) = manager.get_game(game_id) {
-                            let game_state = ServerMessage::GameState(game.clone());
-                            ctx.text(serde_json::to_string(&game_state).unwrap());
+                            room.do_send(Broadcast(ServerMessage::GameState(Box::new(
+                                game.clone(),
+                            ))));
                         }
+                        actix::spawn(game_manager::play_bots(game_manager.clone(), game_id));
                     }
                     Err(e) => {
                         let response = ServerMessage::Error(e);
@@ -61,18 +160,22 @@ impl GameWebSocket {
                 game_name,
                 player_name,
             } => {
-                let mut manager = self.game_manager.try_lock().unwrap();
-                match manager.join_game(game_name, player_name) {
+                let game_manager = self.game_manager.clone();
+                let mut manager = game_manager.lock().unwrap();
+                match manager.join_game(game_name, player_name.clone()) {
                     Ok((game_id, player_id)) => {
-                        self.game_id = Some(game_id);
-                        self.player_id = Some(player_id);
+                        self.enter_game(&mut manager, game_id, player_id);
 
                         let response = ServerMessage::GameJoined { game_id, player_id };
                         ctx.text(serde_json::to_string(&response).unwrap());
 
+                        let room = manager.room(game_id);
+                        self.join_room(room.clone(), player_name, ctx);
+
                         if let Some(game) = manager.get_game(game_id) {
-                            let game_state = ServerMessage::GameState(game.clone());
-                            ctx.text(serde_json::to_string(&game_state).unwrap());
+                            room.do_send(Broadcast(ServerMessage::GameState(Box::new(
+                                game.clone(),
+                            ))));
                         }
                     }
                     Err(e) => {
@@ -81,35 +184,117 @@ impl GameWebSocket {
                     }
                 }
             }
-            ClientMessage::MakeMove { row, col } => {
-                if let (Some(game_id), Some(player_id)) = (self.game_id, self.player_id) {
-                    let mut manager = self.game_manager.try_lock().unwrap();
-                    match manager.make_move(game_id, row, col, player_id) {
-                        Ok(()) => {
-                            if let Some(game) = manager.get_game(game_id) {
-                                let game_state = ServerMessage::GameState(game.clone());
-                                ctx.text(serde_json::to_string(&game_state).unwrap());
-                            }
-                        }
-                        Err(e) => {
-                            let response = ServerMessage::Error(e);
-                            ctx.text(serde_json::to_string(&response).unwrap());
-                        }
-                    }
-                }
+            ClientMessage::MakeMove {
+                cell,
+                sub_board,
+                symbol,
+            } => {
+                self.play_move(placement(cell, sub_board, symbol), ctx);
+            }
+            ClientMessage::DropPiece { col } => {
+                self.play_move(Ok(Move::Drop(col)), ctx);
+            }
+            ClientMessage::PlaceSpooky {
+                cells: [first, second],
+            } => {
+                let mv = Coordinate::try_from(first)
+                    .and_then(|first| Ok(Move::Spooky(first, second.try_into()?)));
+                self.play_move(mv, ctx);
+            }
+            ClientMessage::CollapseMark { cell } => {
+                self.play_move(cell.try_into().map(Move::Collapse), ctx);
+            }
+            ClientMessage::MovePiece { from, to } => {
+                let mv = Coordinate::try_from(from)
+                    .and_then(|from| Ok(Move::Step(from, to.try_into()?)));
+                self.play_move(mv, ctx);
             }
             ClientMessage::GetAvailableGames => {
-                let manager = self.game_manager.try_lock().unwrap();
+                let manager = self.game_manager.lock().unwrap();
                 let games = manager.get_available_games();
                 let response = ServerMessage::AvailableGames(games);
                 ctx.text(serde_json::to_string(&response).unwrap());
             }
+            ClientMessage::Analyze { position } => {
+                let position = match (position, self.game_id) {
+                    (Some(position), _) => Ok(*position),
+                    (None, Some(game_id)) => {
+                        let manager = self.game_manager.lock().unwrap();
+                        manager.view(game_id, self.player_id)
+                    }
+                    (None, None) => Err(GameError::NotInGame),
+                };
+
+                // Solving can take a while, so it runs on a blocking thread and
+                // neither the manager nor this session waits on it.
+                let analysis = web::block(move || analysis::analyze(&position?));
+                ctx.spawn(analysis.into_actor(self).map(|analysis, _, ctx| {
+                    let response = match analysis {
+                        Ok(Ok(analysis)) => ServerMessage::Analysis(analysis),
+                        Ok(Err(e)) => ServerMessage::Error(e),
+                        Err(_) => ServerMessage::Error(GameError::TooComplex),
+                    };
+                    ctx.text(serde_json::to_string(&response).unwrap());
+                }));
+            }
+            ClientMessage::ImportRecord {
+                player_name,
+                record,
+            } => {
+                let game_manager = self.game_manager.clone();
+                let mut manager = game_manager.lock().unwrap();
+                match manager.open_analysis_board(&record, player_name.clone()) {
+                    Ok((game_id, player_id)) => {
+                        self.enter_game(&mut manager, game_id, player_id);
+
+                        let response = ServerMessage::GameCreated { game_id, player_id };
+                        ctx.text(serde_json::to_string(&response).unwrap());
+
+                        let room = manager.room(game_id);
+                        self.join_room(room.clone(), player_name, ctx);
+
+                        if let Some(game) = manager.get_game(game_id) {
+                            room.do_send(Broadcast(ServerMessage::GameState(Box::new(
+                                game.clone(),
+                            ))));
+                        }
+                    }
+                    Err(e) => {
+                        let response = ServerMessage::Error(e);
+                        ctx.text(serde_json::to_string(&response).unwrap());
+                    }
+                }
+            }
+            ClientMessage::Undo => {
+                self.update_game(ctx, |manager, game_id, player_id| {
+                    manager.undo(game_id, player_id)
+                });
+            }
+            ClientMessage::Redo => {
+                self.update_game(ctx, |manager, game_id, player_id| {
+                    manager.redo(game_id, player_id)
+                });
+            }
         }
     }
 }
 
 impl Actor for GameWebSocket {
     type Context = ws::WebsocketContext<Self>;
+
+    fn stopped(&mut self, _: &mut Self::Context) {
+        let game_manager = self.game_manager.clone();
+        self.leave_game(&mut game_manager.lock().unwrap());
+        self.leave_room();
+    }
+}
+
+impl Handler<RoomEvent> for GameWebSocket {
+    type Result = ();
+
+    fn handle(&mut self, msg: RoomEvent, ctx: &mut Self::Context) {
+        ctx.text(serde_json::to_string(&msg.0).unwrap());
+    }
 }
 
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GameWebSocket {
@@ -125,3 +310,20 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GameWebSocket {
         }
     }
 }
+
+/// The move a `MakeMove` message asks for, once its cells are known to be on a board.
+fn placement(
+    cell: RawCoordinate,
+    sub_board: Option<RawCoordinate>,
+    symbol: Option<CellState>,
+) -> Result<Move, GameError> {
+    let cell = cell.try_into()?;
+    let cell = match sub_board {
+        Some(board) => ultimate::global_cell(board.try_into()?, cell)?,
+        None => cell,
+    };
+    Ok(match symbol {
+        Some(symbol) => Move::PlaceSymbol(cell, symbol),
+        None => Move::Place(cell),
+    })
+}
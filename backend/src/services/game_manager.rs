@@ -1,36 +1,56 @@
-use shared::{GameInfo, GameState};
+use crate::services::game_room::{Broadcast, GameRoom};
+use actix::{Actor, Addr};
+use actix_web::web;
+use shared::bot::{self, Difficulty};
+use shared::record::GameRecord;
+use shared::{BoardConfig, GameError, GameInfo, GameState, Move, Role, RulesetId, ServerMessage};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// A computer opponent's turn: what it is allowed to see, and enough about the
+/// game to tell whether it moved on while the bot was thinking.
+pub struct BotTurn {
+    pub difficulty: Difficulty,
+    pub view: GameState,
+    seat: Role,
+    plies: usize,
+}
+
+#[derive(Default)]
 pub struct GameManager {
     games: HashMap<Uuid, GameState>,
     game_names: HashMap<String, Uuid>,
+    rooms: HashMap<Uuid, Addr<GameRoom>>,
 }
 
 impl GameManager {
     pub fn new() -> Self {
-        Self {
-            games: HashMap::new(),
-            game_names: HashMap::new(),
-        }
+        Self::default()
     }
 
+    /// Opens `game` to other players, seating the creator and, if asked for, computer opponents.
     pub fn create_game(
         &mut self,
-        game_name: String,
+        mut game: GameState,
         player_name: String,
-    ) -> Result<(Uuid, Uuid), String> {
-        if self.game_names.contains_key(&game_name) {
-            return Err("Game name already exists".to_string());
+        opponent: Option<Difficulty>,
+    ) -> Result<(Uuid, Uuid), GameError> {
+        if self.game_names.contains_key(&game.name) {
+            return Err(GameError::NameTaken);
         }
 
-        let mut game = GameState::new(game_name.clone());
         let player = game.add_player(player_name)?;
+        if let Some(difficulty) = opponent {
+            while !game.is_full {
+                game.add_bot(difficulty)?;
+            }
+        }
         let game_id = game.id;
         let player_id = player.id;
 
+        self.game_names.insert(game.name.clone(), game_id);
         self.games.insert(game_id, game);
-        self.game_names.insert(game_name, game_id);
 
         Ok((game_id, player_id))
     }
@@ -39,45 +59,247 @@ impl GameManager {
         &mut self,
         game_name: String,
         player_name: String,
-    ) -> Result<(Uuid, Uuid), String> {
-        let game_id = self
+    ) -> Result<(Uuid, Uuid), GameError> {
+        let game_id = *self
             .game_names
             .get(&game_name)
-            .ok_or("Game not found")?
-            .clone();
+            .ok_or(GameError::GameNotFound)?;
+
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or(GameError::GameNotFound)?;
+
+        let player = game.add_player(player_name)?;
+        Ok((game_id, player.id))
+    }
+
+    pub fn make_move(&mut self, game_id: Uuid, mv: Move, player_id: Uuid) -> Result<(), GameError> {
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or(GameError::GameNotFound)?;
+
+        game.make_move(mv, player_id)
+    }
+
+    /// Eliminates a player who left a game that is under way, and frees their
+    /// seat in a game still waiting for players, dropping the game once nobody
+    /// is left in it. Finished games are left alone. Analysis boards are closed,
+    /// since only the session that opened one can reach it.
+    pub fn leave_game(&mut self, game_id: Uuid, player_id: Uuid) -> Result<(), GameError> {
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or(GameError::GameNotFound)?;
+
+        if game.analysis_board {
+            self.close_analysis_board(game_id);
+            return Ok(());
+        }
+        if game.game_over {
+            return Ok(());
+        }
+        if game.is_full {
+            return game.eliminate(player_id);
+        }
+
+        game.remove_player(player_id)?;
+        if game.players.is_empty() {
+            self.game_names.remove(&game.name);
+            self.games.remove(&game_id);
+            self.rooms.remove(&game_id);
+        }
+        Ok(())
+    }
+
+    /// The turn of the computer opponent due to move in `game_id`, if any.
+    pub fn bot_turn(&self, game_id: Uuid) -> Option<BotTurn> {
+        let game = self.games.get(&game_id)?;
+        let difficulty = game.bot_to_move()?;
+        let bot = game
+            .players
+            .iter()
+            .find(|player| player.role == game.current_player)?;
+        Some(BotTurn {
+            difficulty,
+            view: game.view_for(Some(bot.id)),
+            seat: game.current_player,
+            plies: game.history.len(),
+        })
+    }
 
-        let game = self.games.get_mut(&game_id).ok_or("Game not found")?;
+    /// Plays the move a bot chose on its `turn`, unless the game has moved on since.
+    pub fn play_bot_move(
+        &mut self,
+        game_id: Uuid,
+        turn: &BotTurn,
+        mv: Move,
+    ) -> Result<(), GameError> {
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or(GameError::GameNotFound)?;
+
+        if game.bot_to_move().is_none()
+            || game.current_player != turn.seat
+            || game.history.len() != turn.plies
+        {
+            return Err(GameError::NotYourTurn);
+        }
+        game.commit(mv)
+    }
+
+    /// A game as `player_id` sees it, so hidden marks stay hidden.
+    pub fn view(&self, game_id: Uuid, player_id: Option<Uuid>) -> Result<GameState, GameError> {
+        let game = self.games.get(&game_id).ok_or(GameError::GameNotFound)?;
+        Ok(game.view_for(player_id))
+    }
 
+    /// Opens an analysis board replaying `record`, with `player_name` seated to
+    /// move for every side. Analysis boards are not listed or joinable by name.
+    pub fn open_analysis_board(
+        &mut self,
+        record: &str,
+        player_name: String,
+    ) -> Result<(Uuid, Uuid), GameError> {
+        let record = GameRecord::parse(record)?;
+        let name = record.tag("Event").unwrap_or("Analysis").to_string();
+        let mut game = record.replay(name)?;
+        game.analysis_board = true;
         let player = game.add_player(player_name)?;
+
+        let game_id = game.id;
+        self.games.insert(game_id, game);
         Ok((game_id, player.id))
     }
 
-    pub fn make_move(
+    /// Takes back the last move on an analysis board.
+    pub fn undo(&mut self, game_id: Uuid, player_id: Uuid) -> Result<(), GameError> {
+        self.analysis_board(game_id, player_id)?.undo()
+    }
+
+    /// Plays the last move taken back on an analysis board again.
+    pub fn redo(&mut self, game_id: Uuid, player_id: Uuid) -> Result<(), GameError> {
+        self.analysis_board(game_id, player_id)?.redo()
+    }
+
+    /// Drops `game_id` and its room if it is an analysis board.
+    pub fn close_analysis_board(&mut self, game_id: Uuid) {
+        if self
+            .games
+            .get(&game_id)
+            .is_some_and(|game| game.analysis_board)
+        {
+            self.games.remove(&game_id);
+            self.rooms.remove(&game_id);
+        }
+    }
+
+    fn analysis_board(
         &mut self,
         game_id: Uuid,
-        row: usize,
-        col: usize,
         player_id: Uuid,
-    ) -> Result<(), String> {
-        let game = self.games.get_mut(&game_id).ok_or("Game not found")?;
+    ) -> Result<&mut GameState, GameError> {
+        let game = self
+            .games
+            .get_mut(&game_id)
+            .ok_or(GameError::GameNotFound)?;
+
+        if !game.players.iter().any(|player| player.id == player_id) {
+            return Err(GameError::PlayerNotFound);
+        }
+        if !game.analysis_board {
+            return Err(GameError::InvalidMove);
+        }
+        Ok(game)
+    }
 
-        game.make_move(row, col, player_id)
+    /// The record of a finished game, for downloading.
+    pub fn record(&self, game_id: Uuid) -> Result<GameRecord, GameError> {
+        let game = self.games.get(&game_id).ok_or(GameError::GameNotFound)?;
+        if !game.game_over {
+            return Err(GameError::GameInProgress);
+        }
+        GameRecord::from_game(game)
     }
 
     pub fn get_game(&self, game_id: Uuid) -> Option<&GameState> {
         self.games.get(&game_id)
     }
 
+    /// Returns the room broadcasting updates for `game_id`, starting it on first use.
+    pub fn room(&mut self, game_id: Uuid) -> Addr<GameRoom> {
+        self.rooms
+            .entry(game_id)
+            .or_insert_with(|| GameRoom::new(game_id).start())
+            .clone()
+    }
+
     pub fn get_available_games(&self) -> Vec<GameInfo> {
         self.games
             .values()
-            .filter(|game| !game.is_full)
+            .filter(|game| !game.is_full && !game.analysis_board)
             .map(|game| GameInfo {
                 id: game.id,
                 name: game.name.clone(),
                 player_count: game.players.len(),
+                seat_count: game.seats.len(),
                 is_full: game.is_full,
+                ruleset: game.ruleset,
+                config: game.config,
             })
             .collect()
     }
 }
+
+/// Lets computer opponents in `game_id` move until it is a human's turn or the
+/// game ends, showing the room each move.
+///
+/// Bots search a copy of the game on a blocking thread, so the manager stays
+/// unlocked while they think.
+pub async fn play_bots(game_manager: Arc<Mutex<GameManager>>, game_id: Uuid) {
+    loop {
+        let Some(turn) = game_manager.lock().unwrap().bot_turn(game_id) else {
+            return;
+        };
+        let seed = Uuid::new_v4().as_u64_pair().0;
+        let searched = web::block(move || {
+            let mv = bot::choose_move(&turn.view, turn.difficulty, seed);
+            (turn, mv)
+        });
+        let Ok((turn, Some(mv))) = searched.await else {
+            return;
+        };
+
+        let mut manager = game_manager.lock().unwrap();
+        if manager.play_bot_move(game_id, &turn, mv).is_err() {
+            return;
+        }
+        if let Some(game) = manager.get_game(game_id) {
+            let update = ServerMessage::GameState(Box::new(game.clone()));
+            manager.room(game_id).do_send(Broadcast(update));
+        }
+    }
+}
+
+/// Sets up a new game from a position in [`shared::notation`], or from an empty
+/// board of the given ruleset.
+pub fn new_game(
+    game_name: String,
+    ruleset: RulesetId,
+    config: Option<BoardConfig>,
+    seats: Vec<Role>,
+    position: Option<String>,
+) -> Result<GameState, GameError> {
+    if let Some(position) = position {
+        return GameState::from_notation(game_name, &position);
+    }
+
+    let config = config.unwrap_or_else(|| ruleset.ruleset().default_config());
+    let mut game = GameState::with_config(game_name, ruleset, config)?;
+    if !seats.is_empty() {
+        game.set_seats(seats)?;
+    }
+    Ok(game)
+}
@@ -0,0 +1,122 @@
+use actix::{Actor, Context, Handler, Message, Recipient};
+use shared::ServerMessage;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A server message pushed from a room to one of its subscribed sessions.
+#[derive(Message, Clone)]
+#[rtype(result = "()")]
+pub struct RoomEvent(pub ServerMessage);
+
+/// Subscribes a websocket session to the room.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Join {
+    pub session_id: Uuid,
+    /// The seat the session plays, or `None` for a spectator.
+    pub player_id: Option<Uuid>,
+    pub player_name: String,
+    pub addr: Recipient<RoomEvent>,
+}
+
+/// Unsubscribes a websocket session from the room.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Leave {
+    pub session_id: Uuid,
+}
+
+/// Sends a message to every session subscribed to the room; game states are
+/// projected so each session only sees what its seat is allowed to.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Broadcast(pub ServerMessage);
+
+struct Subscriber {
+    player_name: String,
+    player_id: Option<Uuid>,
+    addr: Recipient<RoomEvent>,
+}
+
+/// Fans out game updates to every session taking part in a single game.
+pub struct GameRoom {
+    game_id: Uuid,
+    subscribers: HashMap<Uuid, Subscriber>,
+}
+
+impl GameRoom {
+    pub fn new(game_id: Uuid) -> Self {
+        Self {
+            game_id,
+            subscribers: HashMap::new(),
+        }
+    }
+
+    fn send_to_others(&self, skip: Uuid, message: ServerMessage) {
+        for (session_id, subscriber) in &self.subscribers {
+            if *session_id != skip {
+                subscriber.addr.do_send(RoomEvent(message.clone()));
+            }
+        }
+    }
+}
+
+impl Actor for GameRoom {
+    type Context = Context<Self>;
+}
+
+impl Handler<Join> for GameRoom {
+    type Result = ();
+
+    fn handle(&mut self, msg: Join, _: &mut Context<Self>) {
+        log::debug!("session {} joined room {}", msg.session_id, self.game_id);
+
+        self.send_to_others(
+            msg.session_id,
+            ServerMessage::PlayerConnected {
+                player_name: msg.player_name.clone(),
+            },
+        );
+        self.subscribers.insert(
+            msg.session_id,
+            Subscriber {
+                player_name: msg.player_name,
+                player_id: msg.player_id,
+                addr: msg.addr,
+            },
+        );
+    }
+}
+
+impl Handler<Leave> for GameRoom {
+    type Result = ();
+
+    fn handle(&mut self, msg: Leave, _: &mut Context<Self>) {
+        if let Some(subscriber) = self.subscribers.remove(&msg.session_id) {
+            log::debug!("session {} left room {}", msg.session_id, self.game_id);
+
+            self.send_to_others(
+                msg.session_id,
+                ServerMessage::PlayerDisconnected {
+                    player_name: subscriber.player_name,
+                },
+            );
+        }
+    }
+}
+
+impl Handler<Broadcast> for GameRoom {
+    type Result = ();
+
+    fn handle(&mut self, msg: Broadcast, _: &mut Context<Self>) {
+        for subscriber in self.subscribers.values() {
+            let message = match &msg.0 {
+                ServerMessage::GameState(state) => {
+                    ServerMessage::GameState(Box::new(state.view_for(subscriber.player_id)))
+                }
+                other => other.clone(),
+            };
+            subscriber.addr.do_send(RoomEvent(message));
+        }
+    }
+}
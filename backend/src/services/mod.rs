@@ -1 +1,2 @@
 pub mod game_manager;
+pub mod game_room;
@@ -171,45 +171,595 @@
 // }
 
 use crate::routes::Route;
-use crate::services::websocket::WebSocketService;
+use crate::services::websocket::{sleep, WebSocketService, POLL_INTERVAL_MS};
 use dioxus::prelude::*;
-use shared::{CellState, ClientMessage, GameState, ServerMessage};
+use shared::analysis::Analysis;
+use shared::solver::Value;
+use shared::{
+    morris, notakto, quantum, qubic, ultimate, CellState, ClientMessage, Coordinate, GameState,
+    Move, Role, RulesetId, ServerMessage, SubBoard, VariantState,
+};
 use std::cell::RefCell;
-use std::sync::Arc;
+use std::rc::Rc;
+
+type WebSocketContext = Rc<RefCell<Option<WebSocketService>>>;
+
+fn send_message(ws_service: &WebSocketContext, msg: ClientMessage) {
+    if let Some(service) = ws_service.borrow().as_ref() {
+        let _ = service.send_message(msg);
+    }
+}
+
+fn send_move(ws_service: &WebSocketContext, cell: Coordinate, sub_board: Option<Coordinate>) {
+    send_message(
+        ws_service,
+        ClientMessage::MakeMove {
+            cell: cell.into(),
+            sub_board: sub_board.map(Into::into),
+            symbol: None,
+        },
+    );
+}
+
+fn cell_label(cell: CellState) -> String {
+    match cell {
+        CellState::X => "X".to_string(),
+        CellState::O => "O".to_string(),
+        CellState::Triangle => "△".to_string(),
+        CellState::Square => "□".to_string(),
+        CellState::Number(number) => number.to_string(),
+        CellState::Empty => String::new(),
+    }
+}
+
+/// How many join and leave notices stay on screen.
+const MAX_NOTICES: usize = 3;
+
+/// Shows `notice`, dropping the oldest once there are more than [`MAX_NOTICES`].
+fn push_notice(notices: &mut Signal<Vec<String>>, notice: String) {
+    let mut notices = notices.write();
+    notices.push(notice);
+    if notices.len() > MAX_NOTICES {
+        notices.remove(0);
+    }
+}
+
+/// The seats sharing one place in the final standings.
+fn place_label(place: &[Role]) -> String {
+    place
+        .iter()
+        .map(Role::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether `cell` lies on a line that decided the game.
+fn on_winning_line(game: &GameState, cell: Coordinate) -> bool {
+    game.winning_lines.iter().any(|line| line.contains(&cell))
+}
+
+/// Highlights the cells that won the game, and tints a playable cell by how the
+/// move there turns out once the position is analysed.
+fn cell_class(disabled: bool, winning: bool, evaluation: Option<Value>) -> &'static str {
+    match (disabled, evaluation) {
+        _ if winning => "board-cell board-cell-disabled board-cell-winning",
+        (true, _) => "board-cell board-cell-disabled",
+        (false, Some(Value::Win(_))) => "board-cell analysis-win",
+        (false, Some(Value::Draw)) => "board-cell analysis-draw",
+        (false, Some(Value::Loss(_))) => "board-cell analysis-loss",
+        (false, None) => "board-cell",
+    }
+}
+
+/// A flat grid; in gravity games a click anywhere in a column drops a piece into it,
+/// and when `symbol` is set every placement uses that mark.
+fn grid_board(
+    game: &GameState,
+    ws_service: &WebSocketContext,
+    symbol: Option<CellState>,
+    analysis: Option<&Analysis>,
+) -> Element {
+    let gravity = game.ruleset == RulesetId::Gravity;
+    let open_columns: Vec<bool> = game
+        .board
+        .iter_rows()
+        .next()
+        .map(|top| top.iter().map(|cell| *cell == CellState::Empty).collect())
+        .unwrap_or_default();
+
+    rsx! {
+        div {
+            class: if game.board.cols() > 3 {
+                "board-container board-container-compact"
+            } else {
+                "board-container"
+            },
+            style: "grid-template-columns: repeat({game.board.cols()}, 1fr);",
+            for (row_idx, row) in game.board.iter_rows().enumerate() {
+                for (col_idx, cell) in row.iter().enumerate() {
+                    {
+                        let disabled = game.game_over
+                            || if gravity {
+                                !open_columns[col_idx]
+                            } else {
+                                *cell != CellState::Empty
+                            };
+                        let evaluation = analysis.and_then(|analysis| {
+                            let mv = if gravity {
+                                Move::Drop(col_idx)
+                            } else {
+                                let cell = Coordinate::new(row_idx, col_idx).ok()?;
+                                match symbol {
+                                    Some(symbol) => Move::PlaceSymbol(cell, symbol),
+                                    None => Move::Place(cell),
+                                }
+                            };
+                            analysis.evaluation(mv)
+                        });
+                        rsx! {
+                            button {
+                                key: "{row_idx}-{col_idx}",
+                                class: cell_class(
+                                    disabled,
+                                    Coordinate::new(row_idx, col_idx)
+                                        .is_ok_and(|cell| on_winning_line(game, cell)),
+                                    evaluation,
+                                ),
+                                title: evaluation.map(|value| value.to_string()),
+                                disabled,
+                                onclick: {
+                                    let ws_service = ws_service.clone();
+                                    move |_| {
+                                        if gravity {
+                                            send_message(&ws_service, ClientMessage::DropPiece { col: col_idx });
+                                        } else if let Ok(cell) = Coordinate::new(row_idx, col_idx) {
+                                            send_message(
+                                                &ws_service,
+                                                ClientMessage::MakeMove { cell: cell.into(), sub_board: None, symbol },
+                                            );
+                                        }
+                                    }
+                                },
+                                {cell_label(*cell)}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Nine small boards laid out 3×3, with the board the next move must go in highlighted.
+fn ultimate_board(game: &GameState, ws_service: &WebSocketContext) -> Element {
+    let VariantState::Ultimate(state) = &game.variant else {
+        return grid_board(game, ws_service, None, None);
+    };
+    let sub_boards: Vec<Coordinate> = (0..ultimate::SUB_BOARD_SIZE)
+        .flat_map(|row| {
+            (0..ultimate::SUB_BOARD_SIZE).filter_map(move |col| Coordinate::new(row, col).ok())
+        })
+        .collect();
+
+    rsx! {
+        div {
+            class: "ultimate-board",
+            for board in sub_boards.iter().copied() {
+                {
+                    let status = state.sub_board(board);
+                    let playable = !game.game_over
+                        && status == SubBoard::Open
+                        && state.active_board.is_none_or(|active| active == board);
+                    rsx! {
+                        div {
+                            key: "{board.row()}-{board.col()}",
+                            class: match (status, playable) {
+                                (SubBoard::Won(_), _) if on_winning_line(game, board) => {
+                                    "sub-board sub-board-won sub-board-winning"
+                                }
+                                (SubBoard::Won(_), _) => "sub-board sub-board-won",
+                                (SubBoard::Drawn, _) => "sub-board sub-board-drawn",
+                                (SubBoard::Open, true) => "sub-board sub-board-active",
+                                (SubBoard::Open, false) => "sub-board",
+                            },
+                            for local in sub_boards.iter().copied() {
+                                {
+                                    let cell = ultimate::global_cell(board, local)
+                                        .ok()
+                                        .and_then(|cell| game.board.get(cell))
+                                        .unwrap_or(CellState::Empty);
+                                    let disabled = !playable || cell != CellState::Empty;
+                                    rsx! {
+                                        button {
+                                            key: "{local.row()}-{local.col()}",
+                                            class: if disabled {
+                                                "sub-board-cell board-cell-disabled"
+                                            } else {
+                                                "sub-board-cell"
+                                            },
+                                            disabled,
+                                            onclick: {
+                                                let ws_service = ws_service.clone();
+                                                move |_| send_move(&ws_service, local, Some(board))
+                                            },
+                                            {cell_label(cell)}
+                                        }
+                                    }
+                                }
+                            }
+                            if let SubBoard::Won(mark) = status {
+                                div {
+                                    class: "sub-board-winner",
+                                    {cell_label(mark)}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Notakto's 3×3 boards side by side, with boards that already hold a line greyed out.
+fn notakto_board(game: &GameState, ws_service: &WebSocketContext) -> Element {
+    let VariantState::Notakto(state) = &game.variant else {
+        return grid_board(game, ws_service, None, None);
+    };
+
+    rsx! {
+        div {
+            class: "notakto-boards",
+            for (board, dead) in state.dead_boards.iter().copied().enumerate() {
+                div {
+                    key: "{board}",
+                    class: if dead { "notakto-board notakto-board-dead" } else { "notakto-board" },
+                    for row in 0..notakto::BOARD_SIZE {
+                        for col in 0..notakto::BOARD_SIZE {
+                            {
+                                let cell = Coordinate::new(row, board * notakto::BOARD_SIZE + col).ok();
+                                let mark = cell
+                                    .and_then(|cell| game.board.get(cell))
+                                    .unwrap_or(CellState::Empty);
+                                let disabled = game.game_over || dead || mark != CellState::Empty;
+                                rsx! {
+                                    button {
+                                        key: "{row}-{col}",
+                                        class: if disabled {
+                                            "sub-board-cell board-cell-disabled"
+                                        } else {
+                                            "sub-board-cell"
+                                        },
+                                        class: if cell.is_some_and(|cell| on_winning_line(game, cell)) {
+                                            "board-cell-winning"
+                                        },
+                                        disabled,
+                                        onclick: {
+                                            let ws_service = ws_service.clone();
+                                            move |_| {
+                                                if let Some(cell) = cell {
+                                                    send_move(&ws_service, cell, None);
+                                                }
+                                            }
+                                        },
+                                        {cell_label(mark)}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Quantum board: classical marks are drawn large, spooky marks as subscripted labels.
+///
+/// A spooky move takes two clicks; while a cycle is waiting to collapse, only the
+/// two cells of the closing mark can be clicked.
+fn quantum_board(
+    game: &GameState,
+    ws_service: &WebSocketContext,
+    mut selected: Signal<Option<Coordinate>>,
+) -> Element {
+    let VariantState::Quantum(state) = &game.variant else {
+        return grid_board(game, ws_service, None, None);
+    };
+    let free_cells = game.board.empty_cells().count();
+
+    rsx! {
+        div {
+            class: "board-container",
+            for row in 0..quantum::BOARD_SIZE {
+                for col in 0..quantum::BOARD_SIZE {
+                    {
+                        let cell = Coordinate::new(row, col).ok();
+                        let mark = cell
+                            .and_then(|cell| game.board.get(cell))
+                            .unwrap_or(CellState::Empty);
+                        let spooky: Vec<String> = state
+                            .spooky_marks
+                            .iter()
+                            .filter(|spooky| cell.is_some_and(|cell| spooky.cells.contains(&cell)))
+                            .map(|spooky| format!("{}{}", cell_label(spooky.player), spooky.turn))
+                            .collect();
+                        let pending = state.pending_collapse;
+                        let disabled = game.game_over
+                            || match pending {
+                                Some(pending) => !cell.is_some_and(|cell| pending.cells.contains(&cell)),
+                                None => mark != CellState::Empty,
+                            };
+                        let is_selected = cell.is_some() && *selected.read() == cell;
+                        rsx! {
+                            button {
+                                key: "{row}-{col}",
+                                class: match (disabled, is_selected) {
+                                    _ if cell.is_some_and(|cell| on_winning_line(game, cell)) => {
+                                        "board-cell board-cell-disabled board-cell-winning"
+                                    }
+                                    (true, _) => "board-cell board-cell-disabled",
+                                    (false, true) => "board-cell board-cell-selected",
+                                    (false, false) => "board-cell",
+                                },
+                                disabled,
+                                onclick: {
+                                    let ws_service = ws_service.clone();
+                                    move |_| {
+                                        let Some(cell) = cell else {
+                                            return;
+                                        };
+                                        if pending.is_some() {
+                                            send_message(&ws_service, ClientMessage::CollapseMark { cell: cell.into() });
+                                        } else if free_cells == 1 {
+                                            send_move(&ws_service, cell, None);
+                                        } else {
+                                            let first = *selected.read();
+                                            match first {
+                                                Some(first) if first == cell => selected.set(None),
+                                                Some(first) => {
+                                                    selected.set(None);
+                                                    send_message(
+                                                        &ws_service,
+                                                        ClientMessage::PlaceSpooky { cells: [first.into(), cell.into()] },
+                                                    );
+                                                }
+                                                None => selected.set(Some(cell)),
+                                            }
+                                        }
+                                    }
+                                },
+                                if mark != CellState::Empty {
+                                    {cell_label(mark)}
+                                } else {
+                                    span {
+                                        class: "spooky-marks",
+                                        for label in spooky {
+                                            span {
+                                                class: "spooky-mark",
+                                                "{label}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A flat grid with a picker above it, for games where the mover chooses what to
+/// place: X or O in Order and Chaos, one of their remaining numbers in Numerical.
+fn picker_board(
+    game: &GameState,
+    ws_service: &WebSocketContext,
+    choices: Vec<CellState>,
+    mut symbol: Signal<CellState>,
+    analysis: Option<&Analysis>,
+) -> Element {
+    let current = *symbol.read();
+    let chosen = if choices.contains(&current) {
+        current
+    } else {
+        choices.first().copied().unwrap_or(current)
+    };
+
+    rsx! {
+        div {
+            class: "symbol-picker",
+            for mark in choices {
+                button {
+                    key: "{mark:?}",
+                    class: if mark == chosen { "symbol-button symbol-button-active" } else { "symbol-button" },
+                    onclick: move |_| symbol.set(mark),
+                    {cell_label(mark)}
+                }
+            }
+        }
+        {grid_board(game, ws_service, Some(chosen), analysis)}
+    }
+}
+
+/// Three Men's Morris: placement clicks an empty cell; once all pieces are down,
+/// the first click picks up one of your pieces and the second puts it down.
+fn morris_board(
+    game: &GameState,
+    ws_service: &WebSocketContext,
+    mut selected: Signal<Option<Coordinate>>,
+) -> Element {
+    let Ok(mark) = game.current_mark() else {
+        return grid_board(game, ws_service, None, None);
+    };
+    let placing = morris::pieces_in_hand(game, mark) > 0;
+    let from = *selected.read();
+
+    rsx! {
+        div {
+            class: "board-container",
+            for cell in game.board.coordinates() {
+                {
+                    let piece = game.board.get(cell).unwrap_or(CellState::Empty);
+                    let disabled = game.game_over
+                        || match (placing, from) {
+                            (true, _) => piece != CellState::Empty,
+                            (false, Some(from)) => {
+                                from != cell
+                                    && (piece != CellState::Empty || !morris::adjacent(from, cell))
+                            }
+                            (false, None) => piece != mark,
+                        };
+                    rsx! {
+                        button {
+                            key: "{cell.row()}-{cell.col()}",
+                            class: match (disabled, from == Some(cell)) {
+                                _ if on_winning_line(game, cell) => {
+                                    "board-cell board-cell-disabled board-cell-winning"
+                                }
+                                (true, _) => "board-cell board-cell-disabled",
+                                (false, true) => "board-cell board-cell-selected",
+                                (false, false) => "board-cell",
+                            },
+                            disabled,
+                            onclick: {
+                                let ws_service = ws_service.clone();
+                                move |_| {
+                                    if placing {
+                                        send_move(&ws_service, cell, None);
+                                    } else {
+                                        match from {
+                                            Some(from) if from == cell => selected.set(None),
+                                            Some(from) => {
+                                                selected.set(None);
+                                                send_message(
+                                                    &ws_service,
+                                                    ClientMessage::MovePiece { from: from.into(), to: cell.into() },
+                                                );
+                                            }
+                                            None => selected.set(Some(cell)),
+                                        }
+                                    }
+                                }
+                            },
+                            {cell_label(piece)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The 4×4×4 cube drawn as four stacked 4×4 layers.
+fn qubic_board(game: &GameState, ws_service: &WebSocketContext) -> Element {
+    rsx! {
+        div {
+            class: "qubic-board",
+            for layer in 0..qubic::SIZE {
+                div {
+                    key: "{layer}",
+                    class: "qubic-layer",
+                    span {
+                        class: "qubic-layer-label",
+                        "Layer {layer + 1}"
+                    }
+                    div {
+                        class: "board-container board-container-compact",
+                        style: "grid-template-columns: repeat({qubic::SIZE}, 1fr);",
+                        for row in 0..qubic::SIZE {
+                            for col in 0..qubic::SIZE {
+                                {
+                                    let cell = qubic::cell(layer, row, col).ok();
+                                    let mark = cell
+                                        .and_then(|cell| game.board.get(cell))
+                                        .unwrap_or(CellState::Empty);
+                                    let disabled = game.game_over || mark != CellState::Empty;
+                                    rsx! {
+                                        button {
+                                            key: "{row}-{col}",
+                                            class: if disabled {
+                                                "board-cell board-cell-disabled"
+                                            } else {
+                                                "board-cell"
+                                            },
+                                            class: if cell.is_some_and(|cell| on_winning_line(game, cell)) {
+                                                "board-cell-winning"
+                                            },
+                                            disabled,
+                                            onclick: {
+                                                let ws_service = ws_service.clone();
+                                                move |_| {
+                                                    if let Some(cell) = cell {
+                                                        send_move(&ws_service, cell, None);
+                                                    }
+                                                }
+                                            },
+                                            {cell_label(mark)}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
 
 #[component]
 pub fn GameBoard() -> Element {
     let navigator = use_navigator();
-    let ws_service = use_context::<Option<Arc<RefCell<Option<WebSocketService>>>>>().unwrap();
+    let ws_service = use_context::<Option<Rc<RefCell<Option<WebSocketService>>>>>().unwrap();
 
     let game_state = use_signal(|| None::<GameState>);
     // let player_id = use_signal(|| None::<uuid::Uuid>);
     let error_message = use_signal(|| None::<String>);
+    let selected_cell = use_signal(|| None::<Coordinate>);
+    let selected_symbol = use_signal(|| CellState::X);
+    let analysis = use_signal(|| None::<Analysis>);
+    let notices = use_signal(Vec::<String>::new);
 
-    // Handle WebSocket messages
-    use_effect({
+    // Handle WebSocket messages for as long as the board is shown
+    use_future({
         let ws_service = ws_service.clone();
-        let game_state = game_state.clone();
-        let error_message = error_message.clone();
+        let mut game_state = game_state;
+        let mut error_message = error_message;
+        let mut analysis = analysis;
+        let mut notices = notices;
         move || {
             let ws_service = ws_service.clone();
-            let mut game_state = game_state.clone();
-            let mut error_message = error_message.clone();
-            spawn(async move {
-                if let Some(service) = ws_service.borrow().as_ref() {
-                    while let Some(message) = service.receive_message() {
+            async move {
+                loop {
+                    let service = ws_service.borrow().clone();
+                    while let Some(message) =
+                        service.as_ref().and_then(WebSocketService::receive_message)
+                    {
                         match message {
                             ServerMessage::GameState(state) => {
-                                game_state.set(Some(state));
+                                game_state.set(Some(*state));
+                                analysis.set(None);
+                            }
+                            ServerMessage::Analysis(result) => {
+                                analysis.set(Some(result));
                             }
                             ServerMessage::Error(err) => {
-                                error_message.set(Some(err));
+                                error_message.set(Some(err.to_string()));
+                            }
+                            ServerMessage::PlayerConnected { player_name } => {
+                                push_notice(&mut notices, format!("{player_name} joined the game"));
+                            }
+                            ServerMessage::PlayerDisconnected { player_name } => {
+                                push_notice(&mut notices, format!("{player_name} left the game"));
                             }
                             _ => {}
                         }
                     }
+                    sleep(POLL_INTERVAL_MS).await;
                 }
-            });
+            }
         }
     });
 
@@ -252,6 +802,13 @@ pub fn GameBoard() -> Element {
                     }
                 }
 
+                for notice in notices.read().iter() {
+                    div {
+                        class: "notice-message",
+                        "{notice}"
+                    }
+                }
+
                 if let Some(game) = game_state.read().as_ref() {
                     div {
                         class: "game-board-card",
@@ -269,12 +826,15 @@ pub fn GameBoard() -> Element {
                                     div {
                                         class: "player-info",
                                         span {
-                                            class: if player.symbol == game.current_player && !game.game_over {
-                                                "player-name current-player"
-                                            } else {
-                                                "player-name"
+                                            class: match (
+                                                game.eliminated.contains(&player.role),
+                                                player.role == game.current_player && !game.game_over,
+                                            ) {
+                                                (true, _) => "player-name player-eliminated",
+                                                (false, true) => "player-name current-player",
+                                                (false, false) => "player-name",
                                             },
-                                            "{player.name} ({player.symbol:?})"
+                                            "{player.name} ({player.role})"
                                         }
                                     }
                                 }
@@ -285,10 +845,20 @@ pub fn GameBoard() -> Element {
                         div {
                             class: "game-status",
                             if game.game_over {
+                                if game.seats.len() > 2 {
+                                    ol {
+                                        class: "rankings",
+                                        for place in &game.rankings {
+                                            li {
+                                                {place_label(place)}
+                                            }
+                                        }
+                                    }
+                                }
                                 if let Some(winner) = &game.winner {
                                     p {
                                         class: "winner-message",
-                                        "Winner: {winner:?}!"
+                                        "Winner: {winner}!"
                                     }
                                 } else {
                                     p {
@@ -296,50 +866,92 @@ pub fn GameBoard() -> Element {
                                         "It's a Draw!"
                                     }
                                 }
+                                a {
+                                    class: "download-record",
+                                    href: "http://127.0.0.1:8080/api/games/{game.id}/record",
+                                    download: "",
+                                    "Download Record"
+                                }
                             } else {
                                 p {
                                     class: "turn-message",
-                                    "Current Turn: {game.current_player:?}"
+                                    "Current Turn: {game.current_player}"
+                                }
+                                if let Some(analysis) = analysis.read().as_ref() {
+                                    p {
+                                        class: "analysis-summary",
+                                        "Evaluation: {analysis.value}"
+                                    }
+                                } else {
+                                    button {
+                                        class: "analyze-button",
+                                        onclick: {
+                                            let ws_service = ws_service.clone();
+                                            move |_| {
+                                                send_message(&ws_service, ClientMessage::Analyze { position: None });
+                                            }
+                                        },
+                                        "Analyze"
+                                    }
                                 }
                             }
                         }
 
-                        // Game board
-                        {
-                            let ws_service_for_board = ws_service.clone();
-                            rsx! {
-                                div {
-                                    class: "board-container",
-                                    for (row_idx, row) in game.board.iter().enumerate() {
-                                        for (col_idx, cell) in row.iter().enumerate() {
-                                            button {
-                                                key: "{row_idx}-{col_idx}",
-                                                class: if *cell != CellState::Empty || game.game_over {
-                                                    "board-cell board-cell-disabled"
-                                                } else {
-                                                    "board-cell"
-                                                },
-                                                disabled: *cell != CellState::Empty || game.game_over,
-                                                onclick: {
-                                                    let ws_service = ws_service_for_board.clone();
-                                                    move |_| {
-                                                        if let Some(service) = ws_service.borrow().as_ref() {
-                                                            let msg = ClientMessage::MakeMove { row: row_idx, col: col_idx };
-                                                            service.send_message(msg);
-                                                        }
-                                                    }
-                                                },
-                                                match cell {
-                                                    CellState::X => "X",
-                                                    CellState::O => "O",
-                                                    CellState::Empty => "",
-                                                }
-                                            }
-                                        }
-                                    }
+                        // Stepping through an analysis board
+                        if game.analysis_board {
+                            div {
+                                class: "history-controls",
+                                button {
+                                    class: "history-button",
+                                    disabled: game.history.is_empty(),
+                                    onclick: {
+                                        let ws_service = ws_service.clone();
+                                        move |_| send_message(&ws_service, ClientMessage::Undo)
+                                    },
+                                    "Undo"
+                                }
+                                button {
+                                    class: "history-button",
+                                    disabled: game.undone.is_empty(),
+                                    onclick: {
+                                        let ws_service = ws_service.clone();
+                                        move |_| send_message(&ws_service, ClientMessage::Redo)
+                                    },
+                                    "Redo"
                                 }
                             }
                         }
+
+                        // Game board
+                        match game.ruleset {
+                            RulesetId::Ultimate => ultimate_board(game, &ws_service),
+                            RulesetId::Qubic => qubic_board(game, &ws_service),
+                            RulesetId::Notakto => notakto_board(game, &ws_service),
+                            RulesetId::Quantum => quantum_board(game, &ws_service, selected_cell),
+                            RulesetId::ThreeMensMorris => morris_board(game, &ws_service, selected_cell),
+                            RulesetId::OrderChaos => picker_board(
+                                game,
+                                &ws_service,
+                                vec![CellState::X, CellState::O],
+                                selected_symbol,
+                                analysis.read().as_ref(),
+                            ),
+                            RulesetId::Numerical => picker_board(
+                                game,
+                                &ws_service,
+                                match &game.variant {
+                                    VariantState::Numerical(state) => state
+                                        .remaining(game.current_player)
+                                        .iter()
+                                        .map(|number| CellState::Number(*number))
+                                        .collect(),
+                                    _ => Vec::new(),
+                                },
+                                selected_symbol,
+                                analysis.read().as_ref(),
+                            ),
+                            _ => grid_board(game, &ws_service, None, analysis.read().as_ref()),
+                        }
                     }
                 } else {
                     div {
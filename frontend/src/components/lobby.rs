@@ -1,28 +1,201 @@
 use crate::routes::Route;
 use crate::services::websocket::WebSocketService;
 use dioxus::prelude::*;
-use shared::{ClientMessage, GameInfo, ServerMessage};
+use shared::bot::Difficulty;
+use shared::{BoardConfig, CellState, ClientMessage, GameInfo, Role, RulesetId, ServerMessage};
 use std::cell::RefCell;
-use std::sync::Arc;
+use std::rc::Rc;
+
+/// Lobby choices: label, ruleset, board and seat order (empty for the ruleset default).
+const GAME_PRESETS: [(&str, RulesetId, BoardConfig, &[Role]); 15] = [
+    (
+        "Classic 3×3",
+        RulesetId::Classic,
+        BoardConfig {
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "4×4, four in a row",
+        RulesetId::Classic,
+        BoardConfig {
+            rows: 4,
+            cols: 4,
+            win_length: 4,
+        },
+        &[],
+    ),
+    (
+        "Gomoku 15×15, five in a row",
+        RulesetId::Classic,
+        BoardConfig {
+            rows: 15,
+            cols: 15,
+            win_length: 5,
+        },
+        &[],
+    ),
+    (
+        "Ultimate",
+        RulesetId::Ultimate,
+        BoardConfig {
+            rows: 9,
+            cols: 9,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Qubic 4×4×4",
+        RulesetId::Qubic,
+        BoardConfig {
+            rows: 16,
+            cols: 4,
+            win_length: 4,
+        },
+        &[],
+    ),
+    (
+        "Gravity 7×6, four in a row",
+        RulesetId::Gravity,
+        BoardConfig {
+            rows: 6,
+            cols: 7,
+            win_length: 4,
+        },
+        &[],
+    ),
+    (
+        "Misère 3×3, three in a row loses",
+        RulesetId::Misere,
+        BoardConfig {
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Notakto, three boards",
+        RulesetId::Notakto,
+        BoardConfig {
+            rows: 3,
+            cols: 9,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Quantum",
+        RulesetId::Quantum,
+        BoardConfig {
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Order and Chaos 6×6",
+        RulesetId::OrderChaos,
+        BoardConfig {
+            rows: 6,
+            cols: 6,
+            win_length: 5,
+        },
+        &[],
+    ),
+    (
+        "Numerical, lines summing to 15",
+        RulesetId::Numerical,
+        BoardConfig {
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Three Men's Morris",
+        RulesetId::ThreeMensMorris,
+        BoardConfig {
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Fog 4×4, hidden marks",
+        RulesetId::Fog,
+        BoardConfig {
+            rows: 4,
+            cols: 4,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Three players 6×6, three in a row",
+        RulesetId::FreeForAll,
+        BoardConfig {
+            rows: 6,
+            cols: 6,
+            win_length: 3,
+        },
+        &[],
+    ),
+    (
+        "Four players 8×8, three in a row",
+        RulesetId::FreeForAll,
+        BoardConfig {
+            rows: 8,
+            cols: 8,
+            win_length: 3,
+        },
+        &[
+            Role::Mark(CellState::X),
+            Role::Mark(CellState::O),
+            Role::Mark(CellState::Triangle),
+            Role::Mark(CellState::Square),
+        ],
+    ),
+];
+
+/// Who fills the other seats: people joining from the lobby, or the computer.
+const OPPONENTS: [(&str, Option<Difficulty>); 5] = [
+    ("Human players", None),
+    ("Computer, random", Some(Difficulty::Random)),
+    ("Computer, heuristic", Some(Difficulty::Heuristic)),
+    ("Computer, imperfect", Some(Difficulty::Imperfect)),
+    ("Computer, perfect", Some(Difficulty::Perfect)),
+];
 
 #[component]
 pub fn Lobby() -> Element {
     let navigator = use_navigator();
     // let ws_service = use_context::<Option<Arc<RefCell<Option<WebSocketService>>>>>().unwrap();
-    let ws_service_option: Option<Arc<RefCell<Option<WebSocketService>>>> = use_context();
+    let ws_service_option: Option<Rc<RefCell<Option<WebSocketService>>>> = use_context();
     let ws_service = match ws_service_option {
         Some(context) => context,
         None => {
             // Panic with a message indicating the context was missing where expected.
-            panic!("Failed to get WebSocket context in Lobby. Ensure App component provides a context of type Arc<RefCell<Option<WebSocketService>>>.");
+            panic!("Failed to get WebSocket context in Lobby. Ensure App component provides a context of type Rc<RefCell<Option<WebSocketService>>>.");
         }
     };
-    let mut player_name = use_signal(|| String::new());
-    let mut game_name = use_signal(|| String::new());
-    let available_games = use_signal(|| Vec::<GameInfo>::new());
+    let mut player_name = use_signal(String::new);
+    let mut game_name = use_signal(String::new);
+    let available_games = use_signal(Vec::<GameInfo>::new);
     let mut selected_game = use_signal(|| None::<String>);
     let error_message = use_signal(|| None::<String>);
     let mut is_creating = use_signal(|| false);
+    let mut game_preset = use_signal(|| 0usize);
+    let mut opponent = use_signal(|| None::<Difficulty>);
+    let mut position = use_signal(String::new);
+    let mut record = use_signal(String::new);
 
     // Initialize WebSocket if not already done
     use_effect({
@@ -30,10 +203,10 @@ pub fn Lobby() -> Element {
         move || {
             let ws_service = ws_service.clone();
             spawn(async move {
-                let mut service = ws_service.borrow_mut();
-                if service.is_none() {
+                if ws_service.borrow().is_none() {
                     let new_service = WebSocketService::new("ws://127.0.0.1:8080/api/ws").await;
-                    *service = Some(new_service.expect("Failed to create WebSocketService"));
+                    *ws_service.borrow_mut() =
+                        Some(new_service.expect("Failed to create WebSocketService"));
                 }
             });
         }
@@ -42,14 +215,10 @@ pub fn Lobby() -> Element {
     // Handle WebSocket messages
     use_effect({
         let ws_service = ws_service.clone();
-        let available_games = available_games.clone();
-        let navigator = navigator.clone();
-        let error_message = error_message.clone();
+        let mut available_games = available_games;
+        let mut error_message = error_message;
         move || {
             let ws_service = ws_service.clone();
-            let mut available_games = available_games.clone();
-            let navigator = navigator.clone();
-            let mut error_message = error_message.clone();
             spawn(async move {
                 if let Some(service) = ws_service.borrow().as_ref() {
                     while let Some(message) = service.receive_message() {
@@ -62,7 +231,7 @@ pub fn Lobby() -> Element {
                                 available_games.set(games);
                             }
                             ServerMessage::Error(err) => {
-                                error_message.set(Some(err));
+                                error_message.set(Some(err.to_string()));
                             }
                             _ => {}
                         }
@@ -75,7 +244,14 @@ pub fn Lobby() -> Element {
     let handle_create_game = {
         let ws_service = ws_service.clone();
         move |_| {
-            to_owned![player_name, game_name, error_message];
+            to_owned![
+                player_name,
+                game_name,
+                game_preset,
+                opponent,
+                position,
+                error_message
+            ];
             if player_name.read().is_empty() || game_name.read().is_empty() {
                 error_message.set(Some(
                     "Please enter both player name and game name".to_string(),
@@ -87,8 +263,14 @@ pub fn Lobby() -> Element {
                 let msg = ClientMessage::CreateGame {
                     game_name: game_name.read().clone(),
                     player_name: player_name.read().clone(),
+                    ruleset: GAME_PRESETS[*game_preset.read()].1,
+                    config: Some(GAME_PRESETS[*game_preset.read()].2),
+                    seats: GAME_PRESETS[*game_preset.read()].3.to_vec(),
+                    opponent: *opponent.read(),
+                    position: Some(position.read().trim().to_string())
+                        .filter(|position| !position.is_empty()),
                 };
-                service.send_message(msg);
+                let _ = service.send_message(msg);
             }
         }
     };
@@ -109,17 +291,36 @@ pub fn Lobby() -> Element {
                         game_name,
                         player_name: player_name.read().clone(),
                     };
-                    service.send_message(msg);
+                    let _ = service.send_message(msg);
                 }
             }
         }
     };
 
+    let handle_open_record = {
+        let ws_service = ws_service.clone();
+        move |_| {
+            to_owned![player_name, record, error_message];
+            if player_name.read().is_empty() || record.read().trim().is_empty() {
+                error_message.set(Some("Please enter your name and a game record".to_string()));
+                return;
+            }
+
+            if let Some(service) = ws_service.borrow().as_ref() {
+                let msg = ClientMessage::ImportRecord {
+                    player_name: player_name.read().clone(),
+                    record: record.read().clone(),
+                };
+                let _ = service.send_message(msg);
+            }
+        }
+    };
+
     let handle_refresh_games = {
         let ws_service = ws_service.clone();
         move |_| {
             if let Some(service) = ws_service.borrow().as_ref() {
-                service.send_message(ClientMessage::GetAvailableGames);
+                let _ = service.send_message(ClientMessage::GetAvailableGames);
             }
         }
     };
@@ -193,11 +394,101 @@ pub fn Lobby() -> Element {
                                     oninput: move |evt| game_name.set(evt.value()),
                                 }
                             }
+                            div {
+                                class: "form-group",
+                                label {
+                                    class: "form-label",
+                                    "Game Type:"
+                                }
+                                select {
+                                    class: "form-input",
+                                    onchange: move |evt| {
+                                        if let Ok(index) = evt.value().parse::<usize>() {
+                                            game_preset.set(index);
+                                        }
+                                    },
+                                    for (index, (label, ..)) in GAME_PRESETS.iter().enumerate() {
+                                        option {
+                                            value: "{index}",
+                                            selected: *game_preset.read() == index,
+                                            "{label}"
+                                        }
+                                    }
+                                }
+                            }
+                            div {
+                                class: "form-group",
+                                label {
+                                    class: "form-label",
+                                    "Opponent:"
+                                }
+                                select {
+                                    class: "form-input",
+                                    onchange: move |evt| {
+                                        let choice = evt.value().parse::<usize>().ok();
+                                        opponent.set(choice.and_then(|index| OPPONENTS.get(index)).and_then(|(_, bot)| *bot));
+                                    },
+                                    for (index, (label, bot)) in OPPONENTS.iter().enumerate() {
+                                        option {
+                                            value: "{index}",
+                                            selected: *opponent.read() == *bot,
+                                            "{label}"
+                                        }
+                                    }
+                                }
+                            }
+                            div {
+                                class: "form-group",
+                                label {
+                                    class: "form-label",
+                                    "Start Position (optional):"
+                                }
+                                input {
+                                    class: "form-input",
+                                    r#type: "text",
+                                    placeholder: "e.g. 3x3:3 classic x1o/1x1/2o x 5",
+                                    value: "{position}",
+                                    oninput: move |evt| position.set(evt.value()),
+                                }
+                            }
                             button {
                                 class: "create-button",
                                 onclick: handle_create_game,
                                 "Create Game"
                             }
+                            div {
+                                class: "form-group record-group",
+                                label {
+                                    class: "form-label",
+                                    "Game Record:"
+                                }
+                                input {
+                                    class: "form-input",
+                                    r#type: "file",
+                                    accept: ".txt,.pgn",
+                                    onchange: move |evt| async move {
+                                        if let Some(files) = evt.files() {
+                                            for name in files.files() {
+                                                if let Some(text) = files.read_file_to_string(&name).await {
+                                                    record.set(text);
+                                                }
+                                            }
+                                        }
+                                    },
+                                }
+                                textarea {
+                                    class: "form-input record-input",
+                                    rows: "6",
+                                    placeholder: "Or paste a game record to study it",
+                                    value: "{record}",
+                                    oninput: move |evt| record.set(evt.value()),
+                                }
+                            }
+                            button {
+                                class: "create-button",
+                                onclick: handle_open_record,
+                                "Open for Analysis"
+                            }
                         }
                     } else {
                         div {
@@ -243,7 +534,11 @@ pub fn Lobby() -> Element {
                                                 }
                                                 span {
                                                     class: "player-count",
-                                                    "Players: {game.player_count}/2"
+                                                    "Players: {game.player_count}/{game.seat_count}"
+                                                }
+                                                span {
+                                                    class: "player-count",
+                                                    "{game.ruleset:?} {game.config.rows}×{game.config.cols}"
                                                 }
                                             }
                                         }
@@ -81,14 +81,10 @@
 // }
 
 use dioxus::{logger::tracing::Level, prelude::*};
-mod components;
-mod routes;
-mod services;
-mod utils;
-use crate::routes::Route;
-use services::websocket::WebSocketService;
+use frontend::routes::Route;
+use frontend::services::websocket::WebSocketService;
 use std::cell::RefCell;
-use std::sync::Arc;
+use std::rc::Rc;
 extern crate console_error_panic_hook;
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
@@ -119,11 +115,11 @@ fn App() -> Element {
 #[component]
 fn WebSocketProvider() -> Element {
     // Create and provide the WebSocket context
-    let ws_service = use_signal(|| Arc::new(RefCell::new(None::<WebSocketService>)));
+    let ws_service = use_signal(|| Rc::new(RefCell::new(None::<WebSocketService>)));
 
     // Initialize WebSocket connection
     use_effect({
-        let mut ws_service = ws_service.clone();
+        let mut ws_service = ws_service;
         move || {
             spawn(async move {
                 match WebSocketService::new("ws://127.0.0.1:8080/api/ws").await {
@@ -152,7 +148,7 @@ fn WebSocketProvider() -> Element {
 #[component]
 fn WebSocketProviderAlternative() -> Element {
     // Provide context at the top level
-    use_context_provider(|| Arc::new(RefCell::new(None::<WebSocketService>)));
+    use_context_provider(|| Rc::new(RefCell::new(None::<WebSocketService>)));
 
     rsx! {
         WebSocketInitializer {}
@@ -163,7 +159,7 @@ fn WebSocketProviderAlternative() -> Element {
 #[component]
 fn WebSocketInitializer() -> Element {
     // Get the context
-    let ws_service_context = use_context::<Arc<RefCell<Option<WebSocketService>>>>();
+    let ws_service_context = use_context::<Rc<RefCell<Option<WebSocketService>>>>();
 
     use_effect({
         let ws_service_context = ws_service_context.clone();
@@ -194,7 +190,7 @@ fn WebSocketProviderBest() -> Element {
 
     // Initialize WebSocket connection
     use_effect({
-        let mut ws_service = ws_service.clone();
+        let mut ws_service = ws_service;
         move || {
             spawn(async move {
                 match WebSocketService::new("ws://127.0.0.1:8080/api/ws").await {
@@ -105,7 +105,7 @@ impl WebSocketService {
         onmessage_callback.forget();
 
         // Set up error handling
-        let onerror_callback = Closure::wrap(Box::new(move |e: ErrorEvent| {
+        let onerror_callback = Closure::wrap(Box::new(move |_: ErrorEvent| {
             web_sys::console::log_1(&"WebSocket error".into());
         }) as Box<dyn FnMut(ErrorEvent)>);
 
@@ -113,7 +113,7 @@ impl WebSocketService {
         onerror_callback.forget();
 
         // Set up close handling
-        let onclose_callback = Closure::wrap(Box::new(move |e: CloseEvent| {
+        let onclose_callback = Closure::wrap(Box::new(move |_: CloseEvent| {
             web_sys::console::log_1(&"WebSocket closed".into());
         }) as Box<dyn FnMut(CloseEvent)>);
 
@@ -143,6 +143,19 @@ impl WebSocketService {
     }
 }
 
+/// How long components wait between checks of the message queue.
+pub const POLL_INTERVAL_MS: i32 = 50;
+
+/// Resolves after `millis` milliseconds, letting the browser get on with other work.
+pub async fn sleep(millis: i32) {
+    let delay = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(delay).await;
+}
+
 // Alternative implementation if you want to keep the original signature
 // (but this is less safe as it can panic)
 impl WebSocketService {
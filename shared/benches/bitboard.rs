@@ -0,0 +1,65 @@
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{perft_bitboard, perft_game_state, place};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use shared::bitboard::BitPosition;
+use shared::{BoardConfig, Coordinate, GameState, RulesetId};
+
+fn perft(c: &mut Criterion) {
+    let config = BoardConfig::new(5, 5, 4).unwrap();
+    let state = GameState::with_config("bench".into(), RulesetId::Classic, config).unwrap();
+    let mut position = BitPosition::try_from(&state).unwrap();
+
+    let mut group = c.benchmark_group("perft 5×5 depth 3");
+    group.bench_function("game state", |b| {
+        b.iter(|| perft_game_state(black_box(&state), 3))
+    });
+    group.bench_function("bitboard", |b| {
+        b.iter(|| perft_bitboard(black_box(&mut position), 3))
+    });
+    group.finish();
+}
+
+/// A 15×15 board with a scattered, line-free pattern of stones on most cells.
+fn crowded_gomoku() -> GameState {
+    let mut state =
+        GameState::with_config("bench".into(), RulesetId::Classic, BoardConfig::gomoku()).unwrap();
+    for row in 0..15 {
+        for col in 0..15 {
+            if (row * 7 + col * 3) % 5 != 0
+                && (state.play(place(row, col)).is_err() || state.game_over)
+            {
+                return state;
+            }
+        }
+    }
+    state
+}
+
+fn win_check(c: &mut Criterion) {
+    let state = crowded_gomoku();
+    let position = BitPosition::try_from(&state).unwrap();
+    let cells: Vec<Coordinate> = state.board.coordinates().collect();
+
+    let mut group = c.benchmark_group("win check 15×15");
+    group.bench_function("game state", |b| {
+        b.iter(|| {
+            cells
+                .iter()
+                .filter(|cell| state.board.has_line_through(**cell, 5))
+                .count()
+        })
+    });
+    group.bench_function("bitboard", |b| {
+        b.iter(|| {
+            (0..cells.len())
+                .filter(|index| position.completes_line(*index))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, perft, win_check);
+criterion_main!(benches);
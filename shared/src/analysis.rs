@@ -0,0 +1,67 @@
+//! Exact evaluation of every legal move in a position, for coaching displays.
+
+use crate::solver::{self, Solver, Value};
+use crate::{GameError, GameState, Move};
+use serde::{Deserialize, Serialize};
+
+/// The value of playing one move, for the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveEvaluation {
+    pub mv: Move,
+    pub value: Value,
+}
+
+/// A solved position: its value, the value of each legal move, and the line
+/// both sides follow under best play.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Analysis {
+    pub value: Value,
+    pub moves: Vec<MoveEvaluation>,
+    /// Best moves for both sides in turn, from the analysed position to the end of the game.
+    pub principal_variation: Vec<Move>,
+}
+
+impl Analysis {
+    /// The evaluation of `mv`, if it is legal in the analysed position.
+    pub fn evaluation(&self, mv: Move) -> Option<Value> {
+        self.moves
+            .iter()
+            .find(|evaluation| evaluation.mv == mv)
+            .map(|evaluation| evaluation.value)
+    }
+}
+
+/// Solves `state` and evaluates each of its legal moves.
+///
+/// Only positions [`solver::is_solvable`] accepts are analysed, and only if the
+/// search finishes within its budget; anything larger would keep the caller
+/// waiting far too long.
+pub fn analyze(state: &GameState) -> Result<Analysis, GameError> {
+    state.validate()?;
+    if !state.game_over && !solver::is_solvable(state) {
+        return Err(GameError::TooComplex);
+    }
+
+    let mut solver = Solver::new();
+    let moves = solver
+        .analyze(state)?
+        .into_iter()
+        .map(|(mv, value)| MoveEvaluation { mv, value })
+        .collect();
+    let value = solver.solve(state)?.value;
+
+    let mut principal_variation = Vec::new();
+    let mut position = state.clone();
+    while let Some(&mv) = solver.solve(&position)?.best_moves.first() {
+        if position.play(mv).is_err() {
+            break;
+        }
+        principal_variation.push(mv);
+    }
+
+    Ok(Analysis {
+        value,
+        moves,
+        principal_variation,
+    })
+}
@@ -0,0 +1,318 @@
+//! A compact two-player position for search and self-play.
+//!
+//! Each side's stones are a 256-bit set, so boards of up to 256 cells (15×15
+//! gomoku included) fit, and every winning line is a precomputed mask. Placing a
+//! stone and undoing it are a couple of bit operations, and a win is detected by
+//! testing only the masks through the cell just played.
+
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, RulesetId,
+};
+use std::sync::Arc;
+
+/// Most cells a bitboard can hold.
+pub const MAX_CELLS: usize = 256;
+
+const WORDS: usize = MAX_CELLS / 64;
+
+/// A set of cell indices.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Bits([u64; WORDS]);
+
+impl Bits {
+    pub fn contains(&self, index: usize) -> bool {
+        self.0[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.0[index / 64] &= !(1 << (index % 64));
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Whether every cell of `other` is also in `self`.
+    pub fn covers(&self, other: &Bits) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & b == *b)
+    }
+
+    pub fn union(&self, other: &Bits) -> Bits {
+        Bits(std::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+
+    /// The cells in the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(word_index, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    word_index * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// Every winning line of a board shape, and the lines through each cell.
+#[derive(Debug)]
+pub struct WinMasks {
+    rows: usize,
+    cols: usize,
+    lines: Vec<Bits>,
+    through: Vec<Vec<usize>>,
+    all_cells: Bits,
+}
+
+impl WinMasks {
+    pub fn new(config: BoardConfig) -> Result<Self, GameError> {
+        config.validate()?;
+        if config.rows * config.cols > MAX_CELLS {
+            return Err(GameError::InvalidConfig);
+        }
+
+        let BoardConfig {
+            rows,
+            cols,
+            win_length,
+        } = config;
+        let mut lines = Vec::new();
+        for (dr, dc) in [(0isize, 1isize), (1, 0), (1, 1), (1, -1)] {
+            for row in 0..rows {
+                for col in 0..cols {
+                    let cells: Option<Vec<usize>> = (0..win_length as isize)
+                        .map(|step| {
+                            let r = row.checked_add_signed(dr * step).filter(|r| *r < rows)?;
+                            let c = col.checked_add_signed(dc * step).filter(|c| *c < cols)?;
+                            Some(r * cols + c)
+                        })
+                        .collect();
+                    if let Some(cells) = cells {
+                        let mut line = Bits::default();
+                        cells.into_iter().for_each(|index| line.insert(index));
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+
+        let mut through = vec![Vec::new(); rows * cols];
+        for (line_index, line) in lines.iter().enumerate() {
+            for cell in line.iter() {
+                through[cell].push(line_index);
+            }
+        }
+
+        let mut all_cells = Bits::default();
+        (0..rows * cols).for_each(|index| all_cells.insert(index));
+
+        Ok(Self {
+            rows,
+            cols,
+            lines,
+            through,
+            all_cells,
+        })
+    }
+
+    pub fn lines(&self) -> &[Bits] {
+        &self.lines
+    }
+}
+
+/// A classic or misère K-in-a-row position between X and O.
+#[derive(Debug, Clone)]
+pub struct BitPosition {
+    masks: Arc<WinMasks>,
+    ruleset: RulesetId,
+    win_length: usize,
+    /// X's stones, then O's.
+    stones: [Bits; 2],
+    /// 0 when X is to move, 1 for O.
+    to_move: usize,
+}
+
+impl BitPosition {
+    /// An empty board; only the classic and misère rules are supported.
+    pub fn new(ruleset: RulesetId, config: BoardConfig) -> Result<Self, GameError> {
+        if !matches!(ruleset, RulesetId::Classic | RulesetId::Misere) {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(Self {
+            masks: Arc::new(WinMasks::new(config)?),
+            ruleset,
+            win_length: config.win_length,
+            stones: [Bits::default(); 2],
+            to_move: 0,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.masks.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.masks.cols
+    }
+
+    pub fn stones(&self, mark: CellState) -> Option<&Bits> {
+        match mark {
+            CellState::X => Some(&self.stones[0]),
+            CellState::O => Some(&self.stones[1]),
+            _ => None,
+        }
+    }
+
+    pub fn side_to_move(&self) -> CellState {
+        Self::mark(self.to_move)
+    }
+
+    pub fn index(&self, cell: Coordinate) -> Option<usize> {
+        (cell.row() < self.rows() && cell.col() < self.cols())
+            .then(|| cell.row() * self.cols() + cell.col())
+    }
+
+    pub fn coordinate(&self, index: usize) -> Option<Coordinate> {
+        Coordinate::new(index / self.cols(), index % self.cols()).ok()
+    }
+
+    pub fn occupied(&self) -> Bits {
+        self.stones[0].union(&self.stones[1])
+    }
+
+    /// Empty cells, in row-major order.
+    pub fn empty_cells(&self) -> impl Iterator<Item = usize> + '_ {
+        let occupied = self.occupied();
+        (0..self.rows() * self.cols()).filter(move |index| !occupied.contains(*index))
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.occupied().covers(&self.masks.all_cells)
+    }
+
+    /// Places the side to move's stone on the empty cell `index` and passes the turn.
+    pub fn play(&mut self, index: usize) {
+        self.stones[self.to_move].insert(index);
+        self.to_move ^= 1;
+    }
+
+    /// Takes back the stone on `index`, which must have been the last one played.
+    pub fn undo(&mut self, index: usize) {
+        self.to_move ^= 1;
+        self.stones[self.to_move].remove(index);
+    }
+
+    /// Whether the stone on `index` completes a line for its owner.
+    pub fn completes_line(&self, index: usize) -> bool {
+        let Some(side) = (0..2).find(|side| self.stones[*side].contains(index)) else {
+            return false;
+        };
+        self.masks.through[index]
+            .iter()
+            .any(|line| self.stones[side].covers(&self.masks.lines[*line]))
+    }
+
+    /// How the game stands after the stone on `last` was played.
+    pub fn outcome(&self, last: usize) -> Option<Outcome> {
+        if self.completes_line(last) {
+            let mover = Role::Mark(Self::mark(self.to_move ^ 1));
+            Some(Outcome::Win(match self.ruleset {
+                RulesetId::Misere => mover.opponent(),
+                _ => mover,
+            }))
+        } else if self.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+
+    /// Rebuilds the full game state, finished the way [`GameState::play`] would
+    /// leave it if a side has a line or the board is full.
+    pub fn to_game_state(&self, name: String) -> Result<GameState, GameError> {
+        let config = BoardConfig::new(self.rows(), self.cols(), self.win_length)?;
+        let mut state = GameState::with_config(name, self.ruleset, config)?;
+        for (side, stones) in self.stones.iter().enumerate() {
+            for index in stones.iter() {
+                let cell = self.coordinate(index).ok_or(GameError::OutOfBounds)?;
+                state.board.set(cell, Self::mark(side))?;
+            }
+        }
+
+        // The side to move can only hold a line it made before the last stone, so
+        // that line decided the game ahead of any the other side has since made.
+        let mover = self.to_move ^ 1;
+        let decided = [self.to_move, mover].into_iter().find_map(|side| {
+            self.masks
+                .lines
+                .iter()
+                .find(|line| self.stones[side].covers(line))
+                .map(|line| (side, line))
+        });
+
+        match decided {
+            Some((side, line)) => {
+                let mark = Self::mark(side);
+                let index = line.iter().next().ok_or(GameError::InvalidConfig)?;
+                let cell = self.coordinate(index).ok_or(GameError::OutOfBounds)?;
+                let rules = self.ruleset.ruleset();
+                state.current_player = Role::Mark(mark);
+                state.winning_lines = rules.winning_lines(&state, Move::Place(cell));
+                state
+                    .winning_lines
+                    .retain(|line| state.board.get(line[0]) == Some(mark));
+                state.finish(Some(match self.ruleset {
+                    RulesetId::Misere => Role::Mark(mark).opponent(),
+                    _ => Role::Mark(mark),
+                }));
+            }
+            None if self.is_full() => {
+                state.current_player = Role::Mark(Self::mark(mover));
+                state.finish(None);
+            }
+            None => state.current_player = Role::Mark(self.side_to_move()),
+        }
+        Ok(state)
+    }
+
+    fn mark(side: usize) -> CellState {
+        if side == 0 {
+            CellState::X
+        } else {
+            CellState::O
+        }
+    }
+}
+
+impl TryFrom<&GameState> for BitPosition {
+    type Error = GameError;
+
+    /// Fails for variants other than classic and misère, boards over
+    /// [`MAX_CELLS`], and boards holding anything but X and O.
+    fn try_from(state: &GameState) -> Result<Self, Self::Error> {
+        let mut position = BitPosition::new(state.ruleset, state.config)?;
+        for cell in state.board.coordinates() {
+            let side = match state.board.get(cell) {
+                Some(CellState::Empty) => continue,
+                Some(CellState::X) => 0,
+                Some(CellState::O) => 1,
+                _ => return Err(GameError::InvalidConfig),
+            };
+            let index = position.index(cell).ok_or(GameError::OutOfBounds)?;
+            position.stones[side].insert(index);
+        }
+        position.to_move = match state.current_player {
+            Role::Mark(CellState::X) => 0,
+            Role::Mark(CellState::O) => 1,
+            _ => return Err(GameError::InvalidConfig),
+        };
+        Ok(position)
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{zobrist, CellState, Coordinate, GameError};
+use serde::{Deserialize, Serialize};
+
+/// Largest number of rows or columns a board may have.
+pub const MAX_BOARD_SIZE: usize = 19;
+
+/// The four line directions; their opposites are covered by walking both ways.
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Board dimensions and the run length needed to win, chosen when a game is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardConfig {
+    pub rows: usize,
+    pub cols: usize,
+    pub win_length: usize,
+}
+
+impl BoardConfig {
+    pub fn new(rows: usize, cols: usize, win_length: usize) -> Result<Self, GameError> {
+        let config = Self {
+            rows,
+            cols,
+            win_length,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 15×15 five-in-a-row.
+    pub fn gomoku() -> Self {
+        Self {
+            rows: 15,
+            cols: 15,
+            win_length: 5,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), GameError> {
+        let dimensions = 1..=MAX_BOARD_SIZE;
+        if !dimensions.contains(&self.rows)
+            || !dimensions.contains(&self.cols)
+            || self.win_length == 0
+            || self.win_length > self.rows.max(self.cols)
+        {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+        }
+    }
+}
+
+/// A rectangular grid of cells stored row by row.
+///
+/// The board keeps its Zobrist hash up to date on every [`Board::set`]; the hash
+/// is not sent over the wire but rebuilt when a board is deserialized. Boards
+/// larger than [`MAX_BOARD_SIZE`] or whose cells do not fill their dimensions
+/// are refused while deserializing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "BoardData")]
+pub struct Board {
+    rows: usize,
+    cols: usize,
+    cells: Vec<CellState>,
+    #[serde(skip)]
+    zobrist: u64,
+}
+
+#[derive(Deserialize)]
+struct BoardData {
+    rows: usize,
+    cols: usize,
+    cells: Vec<CellState>,
+}
+
+impl TryFrom<BoardData> for Board {
+    type Error = GameError;
+
+    fn try_from(data: BoardData) -> Result<Self, GameError> {
+        let dimensions = 1..=MAX_BOARD_SIZE;
+        if !dimensions.contains(&data.rows)
+            || !dimensions.contains(&data.cols)
+            || data.rows * data.cols != data.cells.len()
+        {
+            return Err(GameError::InvalidConfig);
+        }
+
+        let mut board = Board {
+            rows: data.rows,
+            cols: data.cols,
+            cells: data.cells,
+            zobrist: 0,
+        };
+        board.zobrist = board
+            .coordinates()
+            .filter_map(|cell| Some(zobrist::cell_key(cell, board.get(cell)?)))
+            .fold(0, |hash, key| hash ^ key);
+        Ok(board)
+    }
+}
+
+impl Board {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            cells: vec![CellState::Empty; rows * cols],
+            zobrist: 0,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, cell: Coordinate) -> bool {
+        cell.row() < self.rows && cell.col() < self.cols
+    }
+
+    pub fn get(&self, cell: Coordinate) -> Option<CellState> {
+        self.index(cell).map(|index| self.cells[index])
+    }
+
+    pub fn set(&mut self, cell: Coordinate, state: CellState) -> Result<(), GameError> {
+        let index = self.index(cell).ok_or(GameError::OutOfBounds)?;
+        self.zobrist ^= zobrist::cell_key(cell, self.cells[index]) ^ zobrist::cell_key(cell, state);
+        self.cells[index] = state;
+        Ok(())
+    }
+
+    /// Zobrist hash of the pieces on the board.
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[CellState]> {
+        self.cells.chunks(self.cols)
+    }
+
+    /// Every coordinate on the board, row by row.
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        (0..self.rows).flat_map(move |row| {
+            (0..self.cols).filter_map(move |col| Coordinate::new(row, col).ok())
+        })
+    }
+
+    pub fn empty_cells(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.coordinates()
+            .filter(|cell| self.get(*cell) == Some(CellState::Empty))
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.cells.iter().all(|cell| *cell != CellState::Empty)
+    }
+
+    /// Whether the mark on `cell` is part of a straight run of at least `length` equal marks.
+    ///
+    /// Only lines through `cell` are inspected, so this is the incremental check to
+    /// run after a move instead of rescanning the whole board.
+    pub fn has_line_through(&self, cell: Coordinate, length: usize) -> bool {
+        let Some(mark) = self.get(cell).filter(|mark| *mark != CellState::Empty) else {
+            return false;
+        };
+
+        DIRECTIONS.iter().any(|&(dr, dc)| {
+            let run =
+                1 + self.count_from(cell, mark, dr, dc) + self.count_from(cell, mark, -dr, -dc);
+            run >= length
+        })
+    }
+
+    /// The longest straight run of `mark` anywhere on the board.
+    pub fn longest_run(&self, mark: CellState) -> usize {
+        self.coordinates()
+            .filter(|cell| self.get(*cell) == Some(mark))
+            .flat_map(|cell| {
+                DIRECTIONS
+                    .iter()
+                    .map(move |&(dr, dc)| 1 + self.count_from(cell, mark, dr, dc))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Every straight run of at least `length` equal marks, each from end to end.
+    pub fn runs(&self, length: usize) -> Vec<Vec<Coordinate>> {
+        let mut runs = Vec::new();
+        for cell in self.coordinates() {
+            let Some(mark) = self.get(cell).filter(|mark| *mark != CellState::Empty) else {
+                continue;
+            };
+
+            for &(dr, dc) in &DIRECTIONS {
+                // Walk each run once, from its first cell.
+                let continues = self
+                    .step(cell, -dr, -dc)
+                    .is_some_and(|previous| self.get(previous) == Some(mark));
+                if continues {
+                    continue;
+                }
+
+                let mut run = vec![cell];
+                while let Some(next) = run
+                    .last()
+                    .and_then(|last| self.step(*last, dr, dc))
+                    .filter(|next| self.get(*next) == Some(mark))
+                {
+                    run.push(next);
+                }
+                if run.len() >= length {
+                    runs.push(run);
+                }
+            }
+        }
+        runs
+    }
+
+    fn count_from(&self, cell: Coordinate, mark: CellState, dr: isize, dc: isize) -> usize {
+        let mut count = 0;
+        let mut current = cell;
+        while let Some(next) = self.step(current, dr, dc) {
+            if self.get(next) != Some(mark) {
+                break;
+            }
+            count += 1;
+            current = next;
+        }
+        count
+    }
+
+    fn step(&self, cell: Coordinate, dr: isize, dc: isize) -> Option<Coordinate> {
+        let row = cell.row().checked_add_signed(dr)?;
+        let col = cell.col().checked_add_signed(dc)?;
+        Coordinate::new(row, col)
+            .ok()
+            .filter(|next| self.contains(*next))
+    }
+
+    fn index(&self, cell: Coordinate) -> Option<usize> {
+        self.contains(cell)
+            .then(|| cell.row() * self.cols + cell.col())
+    }
+}
@@ -0,0 +1,116 @@
+//! Computer opponents at several strengths, built on the solver and MCTS.
+
+use crate::mcts::{GreedyRollout, Mcts, MctsConfig, Rng};
+use crate::solver::{self, Solver};
+use crate::{GameState, Move};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How often an imperfect bot ignores its search and plays at random.
+pub const BLUNDER_RATE: f64 = 0.25;
+
+/// Search budget for positions the solver gives up on.
+const MCTS_ITERATIONS: u32 = 3_000;
+const MCTS_TIME_LIMIT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// Any legal move.
+    Random,
+    /// Wins when it can, blocks an immediate loss, and otherwise prefers the centre.
+    #[default]
+    Heuristic,
+    /// Plays like `Perfect`, except for a random move [`BLUNDER_RATE`] of the time.
+    Imperfect,
+    /// Exact play on positions small enough to solve, strong tree search beyond that.
+    Perfect,
+}
+
+impl Difficulty {
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Random => "Random",
+            Difficulty::Heuristic => "Heuristic",
+            Difficulty::Imperfect => "Imperfect",
+            Difficulty::Perfect => "Perfect",
+        }
+    }
+}
+
+/// The move a bot of `difficulty` plays for the side to move, or `None` if there is none.
+pub fn choose_move(state: &GameState, difficulty: Difficulty, seed: u64) -> Option<Move> {
+    let moves = state.legal_moves();
+    if moves.is_empty() {
+        return None;
+    }
+
+    let mut rng = Rng::new(seed);
+    match difficulty {
+        Difficulty::Random => Some(moves[rng.below(moves.len())]),
+        Difficulty::Heuristic => Some(heuristic_move(state, &moves, &mut rng)),
+        Difficulty::Imperfect => {
+            let blunder = (rng.next_u64() % 1000) as f64 / 1000.0 < BLUNDER_RATE;
+            if blunder {
+                Some(moves[rng.below(moves.len())])
+            } else {
+                best_move(state, seed)
+            }
+        }
+        Difficulty::Perfect => best_move(state, seed),
+    }
+}
+
+/// Solves `state` when that is quick, and falls back to tree search when the
+/// position is too large or the solver runs out of budget.
+fn best_move(state: &GameState, seed: u64) -> Option<Move> {
+    if solver::is_solvable(state) {
+        if let Ok(solution) = Solver::new().solve(state) {
+            return solution.best_moves.first().copied();
+        }
+    }
+
+    let config = MctsConfig {
+        iterations: Some(MCTS_ITERATIONS),
+        time_limit: Some(MCTS_TIME_LIMIT),
+        seed,
+        ..MctsConfig::default()
+    };
+    Mcts::with_policy(config, GreedyRollout)
+        .search(state)
+        .best_move
+}
+
+fn heuristic_move(state: &GameState, moves: &[Move], rng: &mut Rng) -> Move {
+    let me = state.current_player;
+    let wins = |state: &GameState, mv: Move| {
+        let mut child = state.clone();
+        child.play(mv).is_ok() && child.winner == Some(state.current_player)
+    };
+
+    if let Some(win) = moves.iter().copied().find(|mv| wins(state, *mv)) {
+        return win;
+    }
+
+    // A cell the next player could win on is worth taking first.
+    let mut opponent_view = state.clone();
+    opponent_view.current_player = state.next_seat(me);
+    if let Some(block) = moves.iter().copied().find(|mv| wins(&opponent_view, *mv)) {
+        return block;
+    }
+
+    let centre = |mv: &Move| match mv {
+        Move::Place(cell) | Move::PlaceSymbol(cell, _) => {
+            let row = cell.row() as f64 - (state.board.rows() as f64 - 1.0) / 2.0;
+            let col = cell.col() as f64 - (state.board.cols() as f64 - 1.0) / 2.0;
+            row * row + col * col
+        }
+        _ => 0.0,
+    };
+    let closest = moves.iter().map(centre).fold(f64::INFINITY, f64::min);
+    let central: Vec<Move> = moves
+        .iter()
+        .copied()
+        .filter(|mv| centre(mv) <= closest)
+        .collect();
+    central[rng.below(central.len())]
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Everything that can go wrong while creating, joining or playing a game.
+///
+/// Errors travel over the wire tagged with a stable snake_case `code`, so
+/// clients can match on them instead of on the English message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum GameError {
+    GameFull,
+    CellOccupied,
+    NotYourTurn,
+    GameOver,
+    OutOfBounds,
+    PlayerNotFound,
+    GameNotFound,
+    NameTaken,
+    NotInGame,
+    InvalidConfig,
+    InvalidMove,
+    WrongSubBoard,
+    ColumnFull,
+    BoardDead,
+    CollapsePending,
+    NumberUnavailable,
+    NotYourPiece,
+    NotAdjacent,
+    TooComplex,
+    NothingToUndo,
+    NothingToRedo,
+    InvalidNotation,
+    InvalidRecord,
+    GameInProgress,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            GameError::GameFull => "Game is full",
+            GameError::CellOccupied => "Cell is already occupied",
+            GameError::NotYourTurn => "Not your turn",
+            GameError::GameOver => "Game is over",
+            GameError::OutOfBounds => "Move is outside the board",
+            GameError::PlayerNotFound => "Player not found",
+            GameError::GameNotFound => "Game not found",
+            GameError::NameTaken => "Game name already exists",
+            GameError::NotInGame => "You have not joined a game",
+            GameError::InvalidConfig => "Invalid board configuration",
+            GameError::InvalidMove => "That move is not allowed in this game",
+            GameError::WrongSubBoard => "You must play in the highlighted board",
+            GameError::ColumnFull => "That column is full",
+            GameError::BoardDead => "That board is already out of play",
+            GameError::CollapsePending => "The entanglement cycle must be collapsed first",
+            GameError::NumberUnavailable => "That number is not yours to play",
+            GameError::NotYourPiece => "You can only move your own pieces",
+            GameError::NotAdjacent => "Pieces can only move to an adjacent empty cell",
+            GameError::TooComplex => "This position is too large to analyse",
+            GameError::NothingToUndo => "There is no move to take back",
+            GameError::NothingToRedo => "There is no move to play again",
+            GameError::InvalidNotation => "That position notation could not be read",
+            GameError::InvalidRecord => "That game record could not be read",
+            GameError::GameInProgress => "The game is still being played",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for GameError {}
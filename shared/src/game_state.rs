@@ -1,144 +1,529 @@
+use crate::{
+    bot::Difficulty, zobrist, Board, BoardConfig, GameError, Move, Outcome, RulesetId,
+    VariantState, MAX_BOARD_SIZE,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Copy)]
 pub enum CellState {
     Empty,
     X,
     O,
+    Triangle,
+    Square,
+    /// A number placed in Numerical tic-tac-toe.
+    Number(u8),
+}
+
+/// The part a player takes in a game, which decides whose turn it is and who won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Role {
+    /// Always places the same mark.
+    Mark(CellState),
+    /// Order and Chaos: wants a line of like marks.
+    Order,
+    /// Order and Chaos: wants the board filled without one.
+    Chaos,
+    /// Numerical tic-tac-toe: places the odd numbers.
+    Odd,
+    /// Numerical tic-tac-toe: places the even numbers.
+    Even,
+}
+
+impl Role {
+    /// The mark this role always places, if it has one.
+    pub fn mark(self) -> Option<CellState> {
+        match self {
+            Role::Mark(mark) => Some(mark),
+            Role::Order | Role::Chaos | Role::Odd | Role::Even => None,
+        }
+    }
+
+    /// The other side in a two-player game.
+    pub fn opponent(self) -> Role {
+        match self {
+            Role::Mark(CellState::X) => Role::Mark(CellState::O),
+            Role::Mark(CellState::O) => Role::Mark(CellState::X),
+            Role::Mark(mark) => Role::Mark(mark),
+            Role::Order => Role::Chaos,
+            Role::Chaos => Role::Order,
+            Role::Odd => Role::Even,
+            Role::Even => Role::Odd,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Role::Mark(mark) => write!(f, "{mark:?}"),
+            Role::Order => f.write_str("Order"),
+            Role::Chaos => f.write_str("Chaos"),
+            Role::Odd => f.write_str("Odd"),
+            Role::Even => f.write_str("Even"),
+        }
+    }
+}
+
+/// A board position that is guaranteed to lie within [`MAX_BOARD_SIZE`].
+///
+/// Out-of-range values are rejected both by [`Coordinate::new`] and when
+/// deserializing. Whether the coordinate fits a particular board is checked by
+/// [`Board::contains`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "RawCoordinate")]
+pub struct Coordinate {
+    row: usize,
+    col: usize,
+}
+
+/// A board position as a client sends it, not yet checked against
+/// [`MAX_BOARD_SIZE`].
+///
+/// Moves carry these so that an out-of-range cell still parses and is refused
+/// with [`GameError::OutOfBounds`] when converted to a [`Coordinate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawCoordinate {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coordinate {
+    pub fn new(row: usize, col: usize) -> Result<Self, GameError> {
+        if row >= MAX_BOARD_SIZE || col >= MAX_BOARD_SIZE {
+            return Err(GameError::OutOfBounds);
+        }
+
+        Ok(Self { row, col })
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+}
+
+impl TryFrom<RawCoordinate> for Coordinate {
+    type Error = GameError;
+
+    fn try_from(raw: RawCoordinate) -> Result<Self, Self::Error> {
+        Coordinate::new(raw.row, raw.col)
+    }
+}
+
+impl From<Coordinate> for RawCoordinate {
+    fn from(cell: Coordinate) -> Self {
+        Self {
+            row: cell.row,
+            col: cell.col,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub id: Uuid,
     pub name: String,
-    pub board: [[CellState; 3]; 3],
-    pub current_player: CellState,
+    pub ruleset: RulesetId,
+    pub config: BoardConfig,
+    pub board: Board,
+    #[serde(default)]
+    pub variant: VariantState,
+    pub current_player: Role,
     pub players: Vec<Player>,
-    pub winner: Option<CellState>,
+    pub winner: Option<Role>,
     pub is_full: bool,
     pub game_over: bool,
+    /// Roles in turn order; joining players take them in this order.
+    #[serde(default)]
+    pub seats: Vec<Role>,
+    /// Seats knocked out by a disconnect, in the order they left.
+    #[serde(default)]
+    pub eliminated: Vec<Role>,
+    /// Final standings once the game is over, best first; seats sharing a place are grouped.
+    #[serde(default)]
+    pub rankings: Vec<Vec<Role>>,
+    /// The lines that decided the game once it is won; on nested boards they
+    /// run through sub-boards instead of cells.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub winning_lines: Vec<Vec<Coordinate>>,
+    /// The position the game was set up from, in [`crate::notation`], when it did
+    /// not start from an empty board.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+    /// Every move played so far, oldest first.
+    #[serde(default)]
+    pub history: Vec<MoveRecord>,
+    /// Moves taken back with [`GameState::undo`], most recently undone last.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub undone: Vec<MoveRecord>,
+    /// Set on boards opened for study rather than play, where whoever is seated
+    /// moves for every side.
+    #[serde(default)]
+    pub analysis_board: bool,
+}
+
+/// One move in a game's history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveRecord {
+    pub player: Role,
+    pub mv: Move,
+    /// When the move was played, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Zobrist hash of the position the move led to.
+    pub hash: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub id: Uuid,
     pub name: String,
-    pub symbol: CellState,
+    pub role: Role,
+    /// Set for computer opponents, with the strength they play at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot: Option<Difficulty>,
 }
 
 impl GameState {
     pub fn new(name: String) -> Self {
+        Self::from_config(name, RulesetId::Classic, BoardConfig::default())
+    }
+
+    pub fn with_config(
+        name: String,
+        ruleset: RulesetId,
+        config: BoardConfig,
+    ) -> Result<Self, GameError> {
+        let config = ruleset.ruleset().board_config(config)?;
+        Ok(Self::from_config(name, ruleset, config))
+    }
+
+    fn from_config(name: String, ruleset: RulesetId, config: BoardConfig) -> Self {
+        let rules = ruleset.ruleset();
+        let seats = rules.roles();
         Self {
             id: Uuid::new_v4(),
             name,
-            board: [[CellState::Empty; 3]; 3],
-            current_player: CellState::X,
+            ruleset,
+            config,
+            board: Board::new(config.rows, config.cols),
+            variant: rules.initial_variant_state(&config),
+            current_player: seats[0],
             players: Vec::new(),
             winner: None,
             is_full: false,
             game_over: false,
+            seats,
+            eliminated: Vec::new(),
+            rankings: Vec::new(),
+            winning_lines: Vec::new(),
+            setup: None,
+            history: Vec::new(),
+            undone: Vec::new(),
+            analysis_board: false,
         }
     }
 
-    pub fn add_player(&mut self, player_name: String) -> Result<Player, String> {
-        if self.players.len() >= 2 {
-            return Err("Game is full".to_string());
+    /// Replaces the default seat order; only possible before anyone has joined.
+    pub fn set_seats(&mut self, seats: Vec<Role>) -> Result<(), GameError> {
+        if !self.players.is_empty() {
+            return Err(GameError::InvalidConfig);
         }
 
-        let symbol = if self.players.is_empty() {
-            CellState::X
-        } else {
-            CellState::O
-        };
+        self.seats = self.ruleset.ruleset().seats(seats)?;
+        self.current_player = self.seats[0];
+        Ok(())
+    }
+
+    pub fn add_player(&mut self, player_name: String) -> Result<Player, GameError> {
+        self.seat_player(player_name, None)
+    }
+
+    /// Seats a computer opponent in the next free seat.
+    pub fn add_bot(&mut self, difficulty: Difficulty) -> Result<Player, GameError> {
+        self.seat_player(
+            format!("Computer ({})", difficulty.label()),
+            Some(difficulty),
+        )
+    }
+
+    /// The computer opponent whose turn it is, if any.
+    pub fn bot_to_move(&self) -> Option<Difficulty> {
+        if self.game_over {
+            return None;
+        }
+
+        self.players
+            .iter()
+            .find(|player| player.role == self.current_player)
+            .and_then(|player| player.bot)
+    }
+
+    fn seat_player(
+        &mut self,
+        player_name: String,
+        bot: Option<Difficulty>,
+    ) -> Result<Player, GameError> {
+        let role = *self
+            .seats
+            .iter()
+            .find(|seat| self.players.iter().all(|player| player.role != **seat))
+            .ok_or(GameError::GameFull)?;
 
         let player = Player {
             id: Uuid::new_v4(),
             name: player_name,
-            symbol,
+            role,
+            bot,
         };
 
         self.players.push(player.clone());
-        self.is_full = self.players.len() == 2;
+        self.is_full = self.players.len() == self.seats.len();
 
         Ok(player)
     }
 
-    pub fn make_move(&mut self, row: usize, col: usize, player_id: Uuid) -> Result<(), String> {
-        if self.game_over {
-            return Err("Game is over".to_string());
+    /// Frees `player_id`'s seat in a game still waiting for players, for the next
+    /// player to join. Once every seat is taken, players leave by being eliminated.
+    pub fn remove_player(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        if self.is_full {
+            return Err(GameError::GameInProgress);
         }
 
-        if self.board[row][col] != CellState::Empty {
-            return Err("Cell is already occupied".to_string());
+        let index = self
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+        self.players.remove(index);
+        Ok(())
+    }
+
+    pub fn make_move(&mut self, mv: Move, player_id: Uuid) -> Result<(), GameError> {
+        if self.game_over {
+            return Err(GameError::GameOver);
         }
 
         let player = self
             .players
             .iter()
             .find(|p| p.id == player_id)
-            .ok_or("Player not found")?;
+            .ok_or(GameError::PlayerNotFound)?;
 
-        if player.symbol != self.current_player {
-            return Err("Not your turn".to_string());
+        if player.role != self.current_player && !self.analysis_board {
+            return Err(GameError::NotYourTurn);
         }
 
-        self.board[row][col] = self.current_player.clone();
+        self.commit(mv)
+    }
 
-        if self.check_winner() {
-            self.winner = Some(self.current_player.clone());
-            self.game_over = true;
-        } else if self.is_board_full() {
-            self.game_over = true;
-        } else {
-            self.current_player = match self.current_player {
-                CellState::X => CellState::O,
-                CellState::O => CellState::X,
-                CellState::Empty => CellState::X,
-            };
+    /// Plays `mv` for the side to move and records it in the history, clearing
+    /// anything left to redo. Timestamps come from the system clock.
+    pub fn commit(&mut self, mv: Move) -> Result<(), GameError> {
+        let player = self.current_player;
+        self.play(mv)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        self.history.push(MoveRecord {
+            player,
+            mv,
+            timestamp,
+            hash: self.zobrist(),
+        });
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Takes back the last move by replaying the rest of the history from the start.
+    pub fn undo(&mut self) -> Result<(), GameError> {
+        let mut history = self.history.clone();
+        let last = history.pop().ok_or(GameError::NothingToUndo)?;
+
+        // Replay into a copy, so a history that no longer replays leaves the game as it was.
+        let mut rewound = self.clone();
+        rewound.rewind();
+        for record in &history {
+            rewound.current_player = record.player;
+            rewound.play(record.mv)?;
         }
+        rewound.history = history;
 
+        // Seats that left stay out, which may leave a single seat standing.
+        let active: Vec<Role> = rewound.active_seats().collect();
+        if let [last] = active[..] {
+            rewound.finish(Some(last));
+        }
+
+        rewound.undone.push(last);
+        *self = rewound;
         Ok(())
     }
 
-    fn check_winner(&self) -> bool {
-        // Check rows, columns, and diagonals
-        for i in 0..3 {
-            if self.board[i][0] != CellState::Empty
-                && self.board[i][0] == self.board[i][1]
-                && self.board[i][1] == self.board[i][2]
-            {
-                return true;
-            }
-            if self.board[0][i] != CellState::Empty
-                && self.board[0][i] == self.board[1][i]
-                && self.board[1][i] == self.board[2][i]
-            {
-                return true;
+    /// Plays the most recently undone move again.
+    pub fn redo(&mut self) -> Result<(), GameError> {
+        let record = *self.undone.last().ok_or(GameError::NothingToRedo)?;
+        if record.player != self.current_player {
+            return Err(GameError::NotYourTurn);
+        }
+
+        self.play(record.mv)?;
+        self.undone.pop();
+        self.history.push(record);
+        Ok(())
+    }
+
+    /// Puts the board back to the starting position, keeping the players and history.
+    pub(crate) fn rewind(&mut self) {
+        let start = self
+            .setup
+            .as_deref()
+            .and_then(|setup| Self::from_notation(String::new(), setup).ok());
+        if let Some(start) = start {
+            self.board = start.board;
+            self.variant = start.variant;
+            self.current_player = start.current_player;
+        } else {
+            let rules = self.ruleset.ruleset();
+            self.board = Board::new(self.config.rows, self.config.cols);
+            self.variant = rules.initial_variant_state(&self.config);
+            let first = self.active_seats().next().unwrap_or(self.seats[0]);
+            self.current_player = first;
+        }
+        self.winner = None;
+        self.game_over = false;
+        self.rankings.clear();
+        self.winning_lines.clear();
+    }
+
+    /// Plays `mv` for the side to move without checking who sent it or recording it;
+    /// this is the fast path search uses.
+    pub fn play(&mut self, mv: Move) -> Result<(), GameError> {
+        if self.game_over {
+            return Err(GameError::GameOver);
+        }
+
+        let rules = self.ruleset.ruleset();
+        rules.apply_move(self, mv)?;
+
+        match rules.outcome(self, mv) {
+            Some(Outcome::Win(winner)) => {
+                self.winning_lines = rules.winning_lines(self, mv);
+                self.finish(Some(winner));
             }
+            Some(Outcome::Draw) => self.finish(None),
+            None => self.current_player = rules.next_player(self, mv),
         }
 
-        // Diagonals
-        if self.board[0][0] != CellState::Empty
-            && self.board[0][0] == self.board[1][1]
-            && self.board[1][1] == self.board[2][2]
-        {
-            return true;
+        Ok(())
+    }
+
+    /// Knocks `player_id` out of a running game. The turn moves on if it was
+    /// theirs, and the last seat standing wins.
+    pub fn eliminate(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        if self.game_over {
+            return Err(GameError::GameOver);
+        }
+
+        let role = self
+            .players
+            .iter()
+            .find(|p| p.id == player_id)
+            .map(|p| p.role)
+            .ok_or(GameError::PlayerNotFound)?;
+        if self.eliminated.contains(&role) {
+            return Ok(());
         }
 
-        if self.board[0][2] != CellState::Empty
-            && self.board[0][2] == self.board[1][1]
-            && self.board[1][1] == self.board[2][0]
-        {
-            return true;
+        self.eliminated.push(role);
+        let active: Vec<Role> = self.active_seats().collect();
+        if let [last] = active[..] {
+            self.finish(Some(last));
+        } else if self.current_player == role {
+            self.current_player = self.next_seat(role);
         }
 
-        false
+        Ok(())
     }
 
-    fn is_board_full(&self) -> bool {
-        self.board
+    /// Seats that are still in play, in turn order.
+    pub fn active_seats(&self) -> impl Iterator<Item = Role> + '_ {
+        self.seats
             .iter()
-            .all(|row| row.iter().all(|cell| *cell != CellState::Empty))
+            .copied()
+            .filter(|role| !self.eliminated.contains(role))
+    }
+
+    /// The first seat after `role` in turn order that has not been eliminated.
+    pub fn next_seat(&self, role: Role) -> Role {
+        let start = self
+            .seats
+            .iter()
+            .position(|seat| *seat == role)
+            .unwrap_or(self.seats.len() - 1);
+        (1..=self.seats.len())
+            .map(|offset| self.seats[(start + offset) % self.seats.len()])
+            .find(|seat| !self.eliminated.contains(seat))
+            .unwrap_or(role)
+    }
+
+    pub(crate) fn finish(&mut self, winner: Option<Role>) {
+        self.winner = winner;
+        self.game_over = true;
+
+        let active = self.active_seats().collect();
+        self.rankings = self.ruleset.ruleset().rankings(self, active);
+        self.rankings
+            .extend(self.eliminated.iter().rev().map(|role| vec![*role]));
+    }
+
+    /// Checks that a state built outside the engine, such as one sent by a client,
+    /// has the board, seats and variant bookkeeping its ruleset calls for.
+    pub fn validate(&self) -> Result<(), GameError> {
+        let expected = Self::with_config(String::new(), self.ruleset, self.config)?;
+        let consistent = expected.config == self.config
+            && expected.board.rows() == self.board.rows()
+            && expected.board.cols() == self.board.cols()
+            && self.variant.fits(&expected.variant, &self.board)
+            && self.seats.contains(&self.current_player);
+        if !consistent {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(())
+    }
+
+    /// The state as the player `player_id` is allowed to see it; anyone who is not
+    /// seated in the game gets the spectator view.
+    pub fn view_for(&self, player_id: Option<Uuid>) -> GameState {
+        let viewer = player_id
+            .and_then(|id| self.players.iter().find(|player| player.id == id))
+            .map(|player| player.role);
+        self.ruleset.ruleset().project(self, viewer)
+    }
+
+    /// Zobrist hash of the board and the side to move.
+    pub fn zobrist(&self) -> u64 {
+        self.board.zobrist() ^ zobrist::role_key(self.current_player)
+    }
+
+    /// The mark the side to move places, for rulesets where each role owns one.
+    pub fn current_mark(&self) -> Result<CellState, GameError> {
+        self.current_player.mark().ok_or(GameError::InvalidMove)
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.game_over {
+            return Vec::new();
+        }
+
+        self.ruleset.ruleset().legal_moves(self)
     }
 }
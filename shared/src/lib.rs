@@ -1,5 +1,20 @@
+pub mod analysis;
+pub mod bitboard;
+pub mod board;
+pub mod bot;
+pub mod error;
 pub mod game_state;
+pub mod mcts;
 pub mod messages;
+pub mod notation;
+pub mod record;
+pub mod ruleset;
+pub mod solver;
+pub mod symmetry;
+pub mod zobrist;
 
+pub use board::*;
+pub use error::*;
 pub use game_state::*;
 pub use messages::*;
+pub use ruleset::*;
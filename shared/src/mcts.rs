@@ -0,0 +1,267 @@
+//! Monte Carlo Tree Search with the UCT selection rule.
+//!
+//! Works for every ruleset through `GameState`'s own move generation, so it
+//! scales to boards far too large to solve exhaustively. Searches are
+//! reproducible: the same seed, budget and position give the same tree.
+
+use crate::{zobrist, GameState, Move, Role};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Rollouts longer than this are scored as draws, so games that can cycle still end.
+const MAX_ROLLOUT_PLIES: usize = 1000;
+
+/// A small deterministic random number generator for search.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        zobrist::split_mix(&mut self.0)
+    }
+
+    /// A number in `0..bound`; `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Picks moves during the random playout phase of each iteration.
+pub trait RolloutPolicy {
+    /// One of `moves`, all legal in `state`, which is never empty.
+    fn choose(&self, state: &GameState, moves: &[Move], rng: &mut Rng) -> Move;
+}
+
+/// Plays uniformly random moves.
+pub struct RandomRollout;
+
+impl RolloutPolicy for RandomRollout {
+    fn choose(&self, _state: &GameState, moves: &[Move], rng: &mut Rng) -> Move {
+        moves[rng.below(moves.len())]
+    }
+}
+
+/// Takes an immediate win when there is one and otherwise plays randomly. Slower
+/// per playout than [`RandomRollout`] but gives far more realistic results.
+pub struct GreedyRollout;
+
+impl RolloutPolicy for GreedyRollout {
+    fn choose(&self, state: &GameState, moves: &[Move], rng: &mut Rng) -> Move {
+        let winning = moves.iter().copied().find(|mv| {
+            let mut child = state.clone();
+            child.play(*mv).is_ok() && child.winner == Some(state.current_player)
+        });
+        winning.unwrap_or_else(|| moves[rng.below(moves.len())])
+    }
+}
+
+/// How long to search and how to balance exploration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MctsConfig {
+    /// Stop after this many iterations. With no time limit either, the default
+    /// count is used.
+    pub iterations: Option<u32>,
+    /// Stop once this much time has passed. Browsers have no clock that `std`
+    /// can read, so WebAssembly callers should rely on `iterations` alone.
+    pub time_limit: Option<Duration>,
+    /// The UCT exploration constant.
+    pub exploration: f64,
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            iterations: Some(10_000),
+            time_limit: None,
+            exploration: std::f64::consts::SQRT_2,
+            seed: 0,
+        }
+    }
+}
+
+/// What the search learned about one move from the root.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MoveStats {
+    pub mv: Move,
+    pub visits: u32,
+    /// Average result for the mover: 1 for a win, ½ for a draw, 0 for a loss.
+    pub value: f64,
+}
+
+/// The result of a search: the most visited move and statistics for every root move,
+/// most visited first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub stats: Vec<MoveStats>,
+    pub iterations: u32,
+}
+
+struct Node {
+    mv: Option<Move>,
+    /// The side that played `mv`, credited with the results below this node.
+    mover: Option<Role>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried: Vec<Move>,
+    visits: u32,
+    reward: f64,
+}
+
+pub struct Mcts<P = RandomRollout> {
+    config: MctsConfig,
+    policy: P,
+}
+
+impl Mcts {
+    pub fn new(config: MctsConfig) -> Self {
+        Self::with_policy(config, RandomRollout)
+    }
+}
+
+impl<P: RolloutPolicy> Mcts<P> {
+    pub fn with_policy(config: MctsConfig, policy: P) -> Self {
+        Self { config, policy }
+    }
+
+    pub fn search(&self, state: &GameState) -> SearchResult {
+        let mut rng = Rng::new(self.config.seed);
+        let mut tree = vec![Node {
+            mv: None,
+            mover: None,
+            parent: None,
+            children: Vec::new(),
+            untried: state.legal_moves(),
+            visits: 0,
+            reward: 0.0,
+        }];
+
+        let started = self.config.time_limit.map(|_| Instant::now());
+        let mut iterations = 0;
+        let has_moves = !tree[0].untried.is_empty();
+        while has_moves && !self.out_of_budget(iterations, started) {
+            self.iterate(state, &mut tree, &mut rng);
+            iterations += 1;
+        }
+
+        let mut stats: Vec<MoveStats> = tree[0]
+            .children
+            .iter()
+            .filter_map(|child| {
+                let node = &tree[*child];
+                Some(MoveStats {
+                    mv: node.mv?,
+                    visits: node.visits,
+                    value: node.reward / f64::from(node.visits.max(1)),
+                })
+            })
+            .collect();
+        stats.sort_by_key(|stats| std::cmp::Reverse(stats.visits));
+
+        SearchResult {
+            best_move: stats.first().map(|best| best.mv),
+            stats,
+            iterations,
+        }
+    }
+
+    /// Whether either limit has been reached; with neither set, the default
+    /// iteration count applies.
+    fn out_of_budget(&self, iterations: u32, started: Option<Instant>) -> bool {
+        let iteration_limit = match self.config {
+            MctsConfig {
+                iterations: None,
+                time_limit: None,
+                ..
+            } => MctsConfig::default().iterations,
+            config => config.iterations,
+        };
+
+        iteration_limit.is_some_and(|limit| iterations >= limit)
+            || started
+                .zip(self.config.time_limit)
+                .is_some_and(|(started, limit)| started.elapsed() >= limit)
+    }
+
+    /// One round of selection, expansion, playout and backpropagation.
+    fn iterate(&self, root: &GameState, tree: &mut Vec<Node>, rng: &mut Rng) {
+        let mut state = root.clone();
+        let mut node = 0;
+
+        while tree[node].untried.is_empty() && !tree[node].children.is_empty() {
+            node = self.select(tree, node);
+            if let Some(mv) = tree[node].mv {
+                let _ = state.play(mv);
+            }
+        }
+
+        if !tree[node].untried.is_empty() && !state.game_over {
+            let untried = &mut tree[node].untried;
+            let mv = untried.swap_remove(rng.below(untried.len()));
+            let mover = state.current_player;
+            if state.play(mv).is_ok() {
+                let child = tree.len();
+                tree.push(Node {
+                    mv: Some(mv),
+                    mover: Some(mover),
+                    parent: Some(node),
+                    children: Vec::new(),
+                    untried: state.legal_moves(),
+                    visits: 0,
+                    reward: 0.0,
+                });
+                tree[node].children.push(child);
+                node = child;
+            }
+        }
+
+        for _ in 0..MAX_ROLLOUT_PLIES {
+            if state.game_over {
+                break;
+            }
+            let moves = state.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            let mv = self.policy.choose(&state, &moves, rng);
+            if state.play(mv).is_err() {
+                break;
+            }
+        }
+
+        let winner = state.winner.filter(|_| state.game_over);
+        let mut current = Some(node);
+        while let Some(index) = current {
+            let node = &mut tree[index];
+            node.visits += 1;
+            node.reward += match (winner, node.mover) {
+                (None, _) => 0.5,
+                (Some(winner), Some(mover)) if winner == mover => 1.0,
+                _ => 0.0,
+            };
+            current = node.parent;
+        }
+    }
+
+    /// The child of `parent` with the best upper confidence bound.
+    fn select(&self, tree: &[Node], parent: usize) -> usize {
+        let log_visits = f64::from(tree[parent].visits.max(1)).ln();
+        let uct = |child: usize| {
+            let node = &tree[child];
+            let visits = f64::from(node.visits.max(1));
+            node.reward / visits + self.config.exploration * (log_visits / visits).sqrt()
+        };
+
+        tree[parent]
+            .children
+            .iter()
+            .copied()
+            .max_by(|a, b| uct(*a).total_cmp(&uct(*b)))
+            .unwrap_or(parent)
+    }
+}
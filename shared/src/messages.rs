@@ -1,4 +1,6 @@
-use crate::GameState;
+use crate::analysis::Analysis;
+use crate::bot::Difficulty;
+use crate::{BoardConfig, CellState, GameError, GameState, RawCoordinate, Role, RulesetId};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -7,25 +9,76 @@ pub enum ClientMessage {
     CreateGame {
         game_name: String,
         player_name: String,
+        #[serde(default)]
+        ruleset: RulesetId,
+        /// Board to play on; the ruleset's default when omitted.
+        #[serde(default)]
+        config: Option<BoardConfig>,
+        /// Turn order of the seats; empty keeps the ruleset's default order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        seats: Vec<Role>,
+        /// Plays against the computer: every other seat is filled by a bot at this strength.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        opponent: Option<Difficulty>,
+        /// Starts from this position, in [`crate::notation`], instead of an empty
+        /// board; its ruleset, board and turn order take the place of the others.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        position: Option<String>,
     },
     JoinGame {
         game_name: String,
         player_name: String,
     },
     MakeMove {
-        row: usize,
+        cell: RawCoordinate,
+        /// On nested boards, the sub-board `cell` is relative to.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sub_board: Option<RawCoordinate>,
+        /// In games where the mover chooses what to place, the mark or number to place.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        symbol: Option<CellState>,
+    },
+    DropPiece {
         col: usize,
     },
+    /// Quantum games: one spooky mark spread over two cells.
+    PlaceSpooky {
+        cells: [RawCoordinate; 2],
+    },
+    /// Quantum games: where the mark that closed an entanglement cycle collapses.
+    CollapseMark {
+        cell: RawCoordinate,
+    },
+    /// Movement-phase games: slides one of your pieces to another cell.
+    MovePiece {
+        from: RawCoordinate,
+        to: RawCoordinate,
+    },
     GetAvailableGames,
+    /// Evaluates every legal move in `position`, or in the joined game when omitted.
+    Analyze {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        position: Option<Box<GameState>>,
+    },
+    /// Opens an analysis board that replays a game record in [`crate::record`] format.
+    ImportRecord {
+        player_name: String,
+        record: String,
+    },
+    /// Analysis boards: takes back the last move.
+    Undo,
+    /// Analysis boards: plays the last move taken back again.
+    Redo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ServerMessage {
     GameCreated { game_id: Uuid, player_id: Uuid },
     GameJoined { game_id: Uuid, player_id: Uuid },
-    GameState(GameState),
+    GameState(Box<GameState>),
+    Analysis(Analysis),
     AvailableGames(Vec<GameInfo>),
-    Error(String),
+    Error(GameError),
     PlayerConnected { player_name: String },
     PlayerDisconnected { player_name: String },
 }
@@ -35,5 +88,8 @@ pub struct GameInfo {
     pub id: Uuid,
     pub name: String,
     pub player_count: usize,
+    pub seat_count: usize,
     pub is_full: bool,
+    pub ruleset: RulesetId,
+    pub config: BoardConfig,
 }
@@ -0,0 +1,359 @@
+//! A one-line text notation for positions, in the spirit of chess's FEN.
+//!
+//! `3x3:3 classic x1o/1x1/2o x 5` is a 3×3 board where three in a row wins,
+//! played under classic rules; its cells row by row from the top, with digits
+//! counting empty cells; X to move; and the fifth move up next. Cells hold `x`, `o`, `t`
+//! (triangle), `s` (square) or `n1`–`n9` for numbers. Ultimate positions add the
+//! sub-board the next move must go in, or `-` for any, and free-for-all positions
+//! their turn order, like `xts`. Quantum positions cannot be written down.
+//!
+//! Cells are named by a column letter and a row number counted from the top, so
+//! `a1` is the top-left corner and `b2` the centre of a 3×3 board.
+
+use crate::ultimate::SUB_BOARD_SIZE;
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Outcome, Role, RulesetId, SubBoard,
+    VariantState,
+};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Every ruleset, for looking them up by code.
+const RULESETS: [RulesetId; 12] = [
+    RulesetId::Classic,
+    RulesetId::Ultimate,
+    RulesetId::Qubic,
+    RulesetId::Gravity,
+    RulesetId::Misere,
+    RulesetId::Notakto,
+    RulesetId::Quantum,
+    RulesetId::OrderChaos,
+    RulesetId::Numerical,
+    RulesetId::ThreeMensMorris,
+    RulesetId::Fog,
+    RulesetId::FreeForAll,
+];
+
+/// The name of `cell`, like `b2`.
+pub fn cell_name(cell: Coordinate) -> String {
+    format!("{}{}", char::from(b'a' + cell.col() as u8), cell.row() + 1)
+}
+
+/// Reads a cell name such as `b2`.
+pub fn parse_cell(name: &str) -> Result<Coordinate, GameError> {
+    let mut chars = name.chars();
+    let col = chars
+        .next()
+        .filter(char::is_ascii_lowercase)
+        .ok_or(GameError::InvalidNotation)?;
+    let row = parse_number(chars.as_str())?;
+    if row == 0 {
+        return Err(GameError::InvalidNotation);
+    }
+
+    Coordinate::new(row - 1, col as usize - 'a' as usize).map_err(|_| GameError::InvalidNotation)
+}
+
+/// The short name of a ruleset, like `order-chaos`.
+pub fn ruleset_code(ruleset: RulesetId) -> &'static str {
+    match ruleset {
+        RulesetId::Classic => "classic",
+        RulesetId::Ultimate => "ultimate",
+        RulesetId::Qubic => "qubic",
+        RulesetId::Gravity => "gravity",
+        RulesetId::Misere => "misere",
+        RulesetId::Notakto => "notakto",
+        RulesetId::Quantum => "quantum",
+        RulesetId::OrderChaos => "order-chaos",
+        RulesetId::Numerical => "numerical",
+        RulesetId::ThreeMensMorris => "morris",
+        RulesetId::Fog => "fog",
+        RulesetId::FreeForAll => "free-for-all",
+    }
+}
+
+pub fn parse_ruleset(code: &str) -> Result<RulesetId, GameError> {
+    RULESETS
+        .into_iter()
+        .find(|ruleset| ruleset_code(*ruleset) == code)
+        .ok_or(GameError::InvalidNotation)
+}
+
+/// The short name of a role: its mark, or `order`, `chaos`, `odd` or `even`.
+pub fn role_code(role: Role) -> String {
+    match role {
+        Role::Mark(mark) => mark_code(mark),
+        Role::Order => "order".to_string(),
+        Role::Chaos => "chaos".to_string(),
+        Role::Odd => "odd".to_string(),
+        Role::Even => "even".to_string(),
+    }
+}
+
+pub fn parse_role(code: &str) -> Result<Role, GameError> {
+    match code {
+        "order" => Ok(Role::Order),
+        "chaos" => Ok(Role::Chaos),
+        "odd" => Ok(Role::Odd),
+        "even" => Ok(Role::Even),
+        _ => {
+            let mut chars = code.chars().peekable();
+            let mark = parse_mark(&mut chars)?;
+            if chars.next().is_some() {
+                return Err(GameError::InvalidNotation);
+            }
+            Ok(Role::Mark(mark))
+        }
+    }
+}
+
+fn mark_code(mark: CellState) -> String {
+    match mark {
+        CellState::Empty => String::new(),
+        CellState::X => "x".to_string(),
+        CellState::O => "o".to_string(),
+        CellState::Triangle => "t".to_string(),
+        CellState::Square => "s".to_string(),
+        CellState::Number(number) => format!("n{number}"),
+    }
+}
+
+/// Reads one mark from the front of `chars`.
+fn parse_mark(chars: &mut Peekable<Chars>) -> Result<CellState, GameError> {
+    let mark = match chars.next() {
+        Some('x') => CellState::X,
+        Some('o') => CellState::O,
+        Some('t') => CellState::Triangle,
+        Some('s') => CellState::Square,
+        Some('n') => {
+            let number = chars
+                .next()
+                .and_then(|digit| digit.to_digit(10))
+                .filter(|number| (1..=9).contains(number))
+                .ok_or(GameError::InvalidNotation)?;
+            CellState::Number(number as u8)
+        }
+        _ => return Err(GameError::InvalidNotation),
+    };
+    Ok(mark)
+}
+
+/// A decimal number without sign or leading zeros.
+fn parse_number(text: &str) -> Result<usize, GameError> {
+    let canonical = !text.is_empty()
+        && text.bytes().all(|byte| byte.is_ascii_digit())
+        && (text == "0" || !text.starts_with('0'));
+    if !canonical {
+        return Err(GameError::InvalidNotation);
+    }
+
+    text.parse().map_err(|_| GameError::InvalidNotation)
+}
+
+/// Reads `rows`x`cols`:`win_length`.
+pub(crate) fn parse_geometry(text: &str) -> Result<BoardConfig, GameError> {
+    let (size, win_length) = text.split_once(':').ok_or(GameError::InvalidNotation)?;
+    let (rows, cols) = size.split_once('x').ok_or(GameError::InvalidNotation)?;
+    BoardConfig::new(
+        parse_number(rows)?,
+        parse_number(cols)?,
+        parse_number(win_length)?,
+    )
+}
+
+fn write_cells(state: &GameState) -> String {
+    state
+        .board
+        .iter_rows()
+        .map(|row| {
+            let mut text = String::new();
+            let mut empty = 0;
+            for cell in row {
+                if *cell == CellState::Empty {
+                    empty += 1;
+                    continue;
+                }
+                if empty > 0 {
+                    text.push_str(&empty.to_string());
+                    empty = 0;
+                }
+                text.push_str(&mark_code(*cell));
+            }
+            if empty > 0 {
+                text.push_str(&empty.to_string());
+            }
+            text
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Fills the board of `state` from its rows; every row must cover the board's
+/// width exactly, and runs of empty cells must be written as a single count.
+fn parse_cells(text: &str, state: &mut GameState) -> Result<(), GameError> {
+    let rows: Vec<&str> = text.split('/').collect();
+    if rows.len() != state.board.rows() {
+        return Err(GameError::InvalidNotation);
+    }
+
+    for (row, cells) in rows.into_iter().enumerate() {
+        let mut chars = cells.chars().peekable();
+        let mut col = 0;
+        let mut after_empty = false;
+        while let Some(&next) = chars.peek() {
+            if next.is_ascii_digit() {
+                let mut count = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    count.push(digit);
+                }
+                let count = parse_number(&count)?;
+                if count == 0 || after_empty {
+                    return Err(GameError::InvalidNotation);
+                }
+                col += count;
+                after_empty = true;
+            } else {
+                let mark = parse_mark(&mut chars)?;
+                let cell = Coordinate::new(row, col).map_err(|_| GameError::InvalidNotation)?;
+                if !state.board.contains(cell) {
+                    return Err(GameError::InvalidNotation);
+                }
+                state.board.set(cell, mark)?;
+                col += 1;
+                after_empty = false;
+            }
+        }
+        if col != state.board.cols() {
+            return Err(GameError::InvalidNotation);
+        }
+    }
+
+    Ok(())
+}
+
+impl GameState {
+    /// This position in notation. Quantum games have no notation.
+    pub fn to_notation(&self) -> Result<String, GameError> {
+        if self.ruleset == RulesetId::Quantum {
+            return Err(GameError::InvalidNotation);
+        }
+
+        let mut fields = vec![
+            format!(
+                "{}x{}:{}",
+                self.config.rows, self.config.cols, self.config.win_length
+            ),
+            ruleset_code(self.ruleset).to_string(),
+            write_cells(self),
+            role_code(self.current_player),
+            self.move_number().to_string(),
+        ];
+        match &self.variant {
+            VariantState::Ultimate(ultimate) => {
+                fields.push(ultimate.active_board.map_or("-".to_string(), cell_name));
+            }
+            _ if self.ruleset == RulesetId::FreeForAll => {
+                fields.push(self.seats.iter().map(|seat| role_code(*seat)).collect());
+            }
+            _ => {}
+        }
+        Ok(fields.join(" "))
+    }
+
+    /// Sets up a game from a position in notation, which it keeps as its start.
+    ///
+    /// Parsing is strict: the board must match what the ruleset plays on, every
+    /// field must be written exactly as [`GameState::to_notation`] would, and a
+    /// position that is already decided comes back as a finished game.
+    pub fn from_notation(name: String, notation: &str) -> Result<Self, GameError> {
+        let fields: Vec<&str> = notation.split(' ').collect();
+        let [geometry, ruleset, cells, side, number, ref extra @ ..] = fields[..] else {
+            return Err(GameError::InvalidNotation);
+        };
+
+        let config = parse_geometry(geometry)?;
+        let ruleset = parse_ruleset(ruleset)?;
+        let mut state = Self::with_config(name, ruleset, config)?;
+        if state.config != config {
+            return Err(GameError::InvalidConfig);
+        }
+
+        match (ruleset, extra) {
+            (RulesetId::FreeForAll, [order]) => {
+                let seats = order
+                    .chars()
+                    .map(|mark| parse_role(mark.encode_utf8(&mut [0; 4])))
+                    .collect::<Result<Vec<_>, _>>()?;
+                state.set_seats(seats)?;
+            }
+            (RulesetId::FreeForAll, _) | (RulesetId::Ultimate, []) => {
+                return Err(GameError::InvalidNotation);
+            }
+            (RulesetId::Ultimate, [_]) | (_, []) => {}
+            _ => return Err(GameError::InvalidNotation),
+        }
+
+        parse_cells(cells, &mut state)?;
+        let side = parse_role(side)?;
+        if !state.seats.contains(&side) {
+            return Err(GameError::InvalidNotation);
+        }
+        state.current_player = side;
+        if parse_number(number)? == 0 {
+            return Err(GameError::InvalidNotation);
+        }
+
+        state.variant = ruleset.ruleset().set_up(&state)?;
+        if let (VariantState::Ultimate(ultimate), [active]) = (&mut state.variant, extra) {
+            if *active != "-" {
+                let board = parse_cell(active)?;
+                let open = board.row() < SUB_BOARD_SIZE
+                    && board.col() < SUB_BOARD_SIZE
+                    && ultimate.sub_board(board) == SubBoard::Open;
+                if !open {
+                    return Err(GameError::InvalidNotation);
+                }
+                ultimate.active_board = Some(board);
+            }
+        }
+
+        state.settle();
+        state.setup = Some(notation.to_string());
+        Ok(state)
+    }
+
+    /// The number of the next move, counting from 1 and carrying on from the
+    /// position the game was set up from.
+    pub fn move_number(&self) -> usize {
+        let first = self
+            .setup
+            .as_deref()
+            .and_then(|setup| setup.split(' ').nth(4))
+            .and_then(|number| number.parse().ok())
+            .unwrap_or(1);
+        first + self.history.len()
+    }
+
+    /// Finishes a set-up position that is already decided, as judged by its
+    /// ruleset from the point of view of the seat that moved last.
+    fn settle(&mut self) {
+        let rules = self.ruleset.ruleset();
+        let side = self.current_player;
+        let seat = self
+            .seats
+            .iter()
+            .position(|seat| *seat == side)
+            .unwrap_or(0);
+        self.current_player = self.seats[(seat + self.seats.len() - 1) % self.seats.len()];
+        let judged = rules.judge(self);
+        self.current_player = side;
+
+        if let Some((outcome, winning_lines)) = judged {
+            self.winning_lines = winning_lines;
+            self.finish(match outcome {
+                Outcome::Win(winner) => Some(winner),
+                Outcome::Draw => None,
+            });
+        }
+    }
+}
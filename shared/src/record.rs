@@ -0,0 +1,379 @@
+//! A plain-text record of a whole game, in the spirit of chess's PGN.
+//!
+//! ```text
+//! [Event "Friday night"]
+//! [Date "2026.10.18"]
+//! [Ruleset "classic"]
+//! [Board "3x3:3"]
+//! [X "Alice"]
+//! [O "Bob"]
+//! [Result "x"]
+//!
+//! b2 a1 c3 c1 a3 b3 c2 x
+//! ```
+//!
+//! Tags come first, one per line, with each seat's player under the seat's name.
+//! `Seats` gives the turn order when it is not the ruleset's usual one, and `Setup`
+//! the starting position in [`crate::notation`] when the game did not begin on an
+//! empty board. The result is the winning seat, `draw`, or `*` for a game still
+//! going, and is repeated after the moves.
+//!
+//! Moves name cells as [`crate::notation`] does. `b2` places a mark, or in gravity
+//! games is the cell the dropped piece lands in; `b2=o` and `b2=n5` place a chosen
+//! mark or number; `a1-b2` slides a piece; `a1~c3` places an entangled pair of
+//! spooky marks; and `@a1` collapses a cycle into `a1`.
+//!
+//! Reading is forgiving so that records edited by hand still load: tags may be
+//! unquoted, and move numbers like `3.`, annotations like `!?` or `$1`, `{comments}`,
+//! `;` comments, `(variations)`, a closing result marker and upper-case letters are
+//! skipped over in the moves.
+
+use crate::notation::{
+    cell_name, parse_cell, parse_geometry, parse_role, parse_ruleset, role_code, ruleset_code,
+};
+use crate::{Coordinate, GameError, GameState, Gravity, Move, Role, RulesetId};
+use std::fmt;
+
+/// Moves are wrapped onto lines no longer than this when written out.
+const LINE_WIDTH: usize = 80;
+
+/// A game's tags and moves, as written in a record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    /// Tag names and values in the order they appear.
+    pub tags: Vec<(String, String)>,
+    /// Moves as written: gravity drops are placements on the cell the piece lands in.
+    pub moves: Vec<Move>,
+}
+
+impl GameRecord {
+    /// Records `state` from its start to the current position.
+    pub fn from_game(state: &GameState) -> Result<Self, GameError> {
+        let rules = state.ruleset.ruleset();
+        let config = state.config;
+        let mut tags = vec![
+            ("Event".to_string(), state.name.clone()),
+            (
+                "Date".to_string(),
+                state
+                    .history
+                    .first()
+                    .map(|record| date(record.timestamp))
+                    .unwrap_or_else(|| "????.??.??".to_string()),
+            ),
+            (
+                "Ruleset".to_string(),
+                ruleset_code(state.ruleset).to_string(),
+            ),
+            (
+                "Board".to_string(),
+                format!("{}x{}:{}", config.rows, config.cols, config.win_length),
+            ),
+        ];
+        if state.seats != rules.roles() {
+            let seats: Vec<String> = state.seats.iter().map(|seat| role_code(*seat)).collect();
+            tags.push(("Seats".to_string(), seats.join(" ")));
+        }
+        if let Some(setup) = &state.setup {
+            tags.push(("Setup".to_string(), setup.clone()));
+        }
+        for player in &state.players {
+            tags.push((player.role.to_string(), player.name.clone()));
+        }
+        tags.push(("Result".to_string(), result(state)));
+
+        // Drops are written as where the piece landed, which takes the position
+        // before each one to work out.
+        let mut replay = state.clone();
+        replay.rewind();
+        let mut moves = Vec::with_capacity(state.history.len());
+        for record in &state.history {
+            replay.current_player = record.player;
+            moves.push(match record.mv {
+                Move::Drop(col) => Move::Place(Gravity::landing_cell(&replay, col)?),
+                mv => mv,
+            });
+            replay.play(record.mv)?;
+        }
+
+        Ok(Self { tags, moves })
+    }
+
+    /// The value of the first tag called `name`, ignoring case.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Reads a record, skipping over anything in the moves that is not a move.
+    pub fn parse(text: &str) -> Result<Self, GameError> {
+        let mut tags = Vec::new();
+        let mut movetext = String::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with('%') {
+                continue;
+            }
+            match line.strip_prefix('[') {
+                Some(tag) => tags.push(parse_tag(tag)?),
+                None => {
+                    movetext.push_str(line);
+                    movetext.push('\n');
+                }
+            }
+        }
+
+        let mut words: Vec<String> = tokens(&movetext)
+            .into_iter()
+            .filter_map(|token| clean_token(&token))
+            .collect();
+        if words.last().is_some_and(|word| is_result(word)) {
+            words.pop();
+        }
+        let moves = words
+            .iter()
+            .map(|word| parse_move(word))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { tags, moves })
+    }
+
+    /// Plays the record out from its start, as a game called `name`.
+    ///
+    /// A missing ruleset means classic and a missing board the ruleset's default.
+    pub fn replay(&self, name: String) -> Result<GameState, GameError> {
+        let mut state = match self.tag("Setup") {
+            Some(setup) => GameState::from_notation(name, setup)?,
+            None => {
+                let ruleset = self
+                    .tag("Ruleset")
+                    .map(|code| parse_ruleset(&code.to_ascii_lowercase()))
+                    .transpose()
+                    .map_err(|_| GameError::InvalidRecord)?
+                    .unwrap_or(RulesetId::Classic);
+                let config = match self.tag("Board") {
+                    Some(board) => parse_geometry(board).map_err(|_| GameError::InvalidRecord)?,
+                    None => ruleset.ruleset().default_config(),
+                };
+                let mut state = GameState::with_config(name, ruleset, config)?;
+                if let Some(seats) = self.tag("Seats") {
+                    let seats = seats
+                        .split_whitespace()
+                        .map(|seat| parse_role(&seat.to_ascii_lowercase()))
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| GameError::InvalidRecord)?;
+                    state.set_seats(seats)?;
+                }
+                state
+            }
+        };
+
+        for mv in &self.moves {
+            let mv = match *mv {
+                Move::Place(cell) if state.ruleset == RulesetId::Gravity => {
+                    if Gravity::landing_cell(&state, cell.col())? != cell {
+                        return Err(GameError::InvalidMove);
+                    }
+                    Move::Drop(cell.col())
+                }
+                mv => mv,
+            };
+            state.commit(mv)?;
+        }
+        Ok(state)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.tags {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{name} \"{value}\"]")?;
+        }
+        writeln!(f)?;
+
+        let words = self
+            .moves
+            .iter()
+            .map(|mv| move_text(*mv))
+            .chain([self.tag("Result").unwrap_or("*").to_string()]);
+        let mut line = String::new();
+        for word in words {
+            if !line.is_empty() && line.len() + 1 + word.len() > LINE_WIDTH {
+                writeln!(f, "{line}")?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        writeln!(f, "{line}")
+    }
+}
+
+/// The result tag of `state`: the winning seat, `draw`, or `*` while it is being played.
+fn result(state: &GameState) -> String {
+    match (state.game_over, state.winner) {
+        (true, Some(winner)) => role_code(winner),
+        (true, None) => "draw".to_string(),
+        (false, _) => "*".to_string(),
+    }
+}
+
+/// The calendar date of `timestamp`, in milliseconds since the Unix epoch, as `YYYY.MM.DD`.
+fn date(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return "????.??.??".to_string();
+    }
+
+    // Howard Hinnant's days-to-civil conversion, with eras starting on 1 March 0000.
+    let days = timestamp / 86_400_000 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!("{year:04}.{month:02}.{day:02}")
+}
+
+/// Reads what follows the `[` of a tag line: a name, then a value that may be quoted.
+fn parse_tag(text: &str) -> Result<(String, String), GameError> {
+    let text = text.trim_end().strip_suffix(']').unwrap_or(text).trim();
+    let (name, value) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    if name.is_empty() {
+        return Err(GameError::InvalidRecord);
+    }
+
+    let value = value.trim();
+    let value = match value.strip_prefix('"') {
+        Some(quoted) => {
+            let mut value = String::new();
+            let mut chars = quoted.chars();
+            while let Some(next) = chars.next() {
+                match next {
+                    '"' => break,
+                    '\\' => value.extend(chars.next()),
+                    _ => value.push(next),
+                }
+            }
+            value
+        }
+        None => value.to_string(),
+    };
+    Ok((name.to_string(), value))
+}
+
+/// Splits the moves into words, dropping comments and variations.
+fn tokens(movetext: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut chars = movetext.chars();
+    let mut variations: usize = 0;
+    while let Some(next) = chars.next() {
+        match next {
+            '{' => {
+                chars.by_ref().find(|next| *next == '}');
+            }
+            ';' => {
+                chars.by_ref().find(|next| *next == '\n');
+            }
+            '(' => variations += 1,
+            ')' => variations = variations.saturating_sub(1),
+            _ if variations > 0 => continue,
+            _ if !next.is_whitespace() => {
+                token.push(next);
+                continue;
+            }
+            _ => {}
+        }
+        if !token.is_empty() {
+            tokens.push(std::mem::take(&mut token));
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Strips a move number and annotations from `token`, or drops it if nothing is left.
+fn clean_token(token: &str) -> Option<String> {
+    if token.starts_with('$') {
+        return None;
+    }
+
+    let token = match token.split_once('.') {
+        Some((number, rest)) if number.bytes().all(|byte| byte.is_ascii_digit()) => {
+            rest.trim_start_matches('.')
+        }
+        _ => token,
+    };
+    let token = token.trim_end_matches(['!', '?', '+', '#']);
+    (!token.is_empty()).then(|| token.to_ascii_lowercase())
+}
+
+/// Whether `token` ends the moves with a result. Cell names never do, even where
+/// they read like a seat, such as `n5` on a gomoku board.
+fn is_result(token: &str) -> bool {
+    parse_cell(token).is_err()
+        && (matches!(token, "*" | "draw" | "1-0" | "0-1" | "1/2-1/2") || parse_role(token).is_ok())
+}
+
+fn parse_move(token: &str) -> Result<Move, GameError> {
+    let cell = |name: &str| parse_cell(name).map_err(|_| GameError::InvalidRecord);
+    let pair = |separator: char| -> Result<(Coordinate, Coordinate), GameError> {
+        let (from, to) = token
+            .split_once(separator)
+            .ok_or(GameError::InvalidRecord)?;
+        Ok((cell(from)?, cell(to)?))
+    };
+
+    if let Some(target) = token.strip_prefix('@') {
+        return Ok(Move::Collapse(cell(target)?));
+    }
+    if token.contains('~') {
+        let (first, second) = pair('~')?;
+        return Ok(Move::Spooky(first, second));
+    }
+    if token.contains('-') {
+        let (from, to) = pair('-')?;
+        return Ok(Move::Step(from, to));
+    }
+    if let Some((target, symbol)) = token.split_once('=') {
+        // A bare number is read as the number mark it stands for.
+        let symbol = if symbol.bytes().all(|byte| byte.is_ascii_digit()) {
+            format!("n{symbol}")
+        } else {
+            symbol.to_string()
+        };
+        let Ok(Role::Mark(mark)) = parse_role(&symbol) else {
+            return Err(GameError::InvalidRecord);
+        };
+        return Ok(Move::PlaceSymbol(cell(target)?, mark));
+    }
+    Ok(Move::Place(cell(token)?))
+}
+
+fn move_text(mv: Move) -> String {
+    match mv {
+        Move::Place(cell) => cell_name(cell),
+        // Only reached for records built by hand; drops are normally written as cells.
+        Move::Drop(col) => format!("{}", char::from(b'a' + col as u8)),
+        Move::Spooky(first, second) => format!("{}~{}", cell_name(first), cell_name(second)),
+        Move::Collapse(cell) => format!("@{}", cell_name(cell)),
+        Move::PlaceSymbol(cell, mark) => {
+            format!("{}={}", cell_name(cell), role_code(Role::Mark(mark)))
+        }
+        Move::Step(from, to) => format!("{}-{}", cell_name(from), cell_name(to)),
+    }
+}
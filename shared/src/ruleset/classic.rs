@@ -0,0 +1,41 @@
+use crate::ruleset::empty_target;
+use crate::{GameError, GameState, Move, Outcome, Role, Ruleset, RulesetId};
+
+/// K-in-a-row on an N×M board; plain tic-tac-toe with the default config.
+pub struct Classic;
+
+impl Ruleset for Classic {
+    fn id(&self) -> RulesetId {
+        RulesetId::Classic
+    }
+
+    fn has_board_symmetry(&self) -> bool {
+        true
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        state.board.empty_cells().map(Move::Place).collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let cell = empty_target(state, mv)?;
+        let mark = state.current_mark()?;
+        state.board.set(cell, mark)
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        let Move::Place(cell) = last_move else {
+            return None;
+        };
+        if state.board.has_line_through(cell, state.config.win_length) {
+            state
+                .board
+                .get(cell)
+                .map(|mark| Outcome::Win(Role::Mark(mark)))
+        } else if state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    Board, BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+use serde::{Deserialize, Serialize};
+
+/// Cells whose hidden mark has been uncovered by a collision and is now visible to everyone.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FogState {
+    pub revealed: Vec<Coordinate>,
+}
+
+/// K-in-a-row where each player only sees their own marks. Playing onto a hidden
+/// opponent mark reveals it and forfeits the turn.
+pub struct Fog;
+
+impl Fog {
+    fn is_revealed(state: &GameState, cell: Coordinate) -> bool {
+        matches!(&state.variant, VariantState::Fog(fog) if fog.revealed.contains(&cell))
+    }
+
+    /// Empties the cells of `board` holding marks `viewer` may not see in `state`.
+    fn hide(board: &mut Board, state: &GameState, viewer: Option<Role>) {
+        let own = viewer.and_then(Role::mark);
+        for cell in state.board.coordinates() {
+            let mark = board.get(cell);
+            if mark != Some(CellState::Empty) && mark != own && !Self::is_revealed(state, cell) {
+                let _ = board.set(cell, CellState::Empty);
+            }
+        }
+    }
+}
+
+impl Ruleset for Fog {
+    fn id(&self) -> RulesetId {
+        RulesetId::Fog
+    }
+
+    fn initial_variant_state(&self, _config: &BoardConfig) -> VariantState {
+        VariantState::Fog(FogState::default())
+    }
+
+    /// Every cell the mover cannot tell is taken: empty cells and hidden opponent marks.
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        let own = state.current_player.mark();
+        state
+            .board
+            .coordinates()
+            .filter(|cell| {
+                let mark = state.board.get(*cell);
+                mark == Some(CellState::Empty) || (mark != own && !Self::is_revealed(state, *cell))
+            })
+            .map(Move::Place)
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let Move::Place(cell) = mv else {
+            return Err(GameError::InvalidMove);
+        };
+        let mark = state.current_mark()?;
+
+        match state.board.get(cell) {
+            None => Err(GameError::OutOfBounds),
+            Some(CellState::Empty) => state.board.set(cell, mark),
+            Some(other) if other == mark || Self::is_revealed(state, cell) => {
+                Err(GameError::CellOccupied)
+            }
+            Some(_) => {
+                if let VariantState::Fog(fog) = &mut state.variant {
+                    fog.revealed.push(cell);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        let Move::Place(cell) = last_move else {
+            return None;
+        };
+        if state.board.has_line_through(cell, state.config.win_length) {
+            state
+                .board
+                .get(cell)
+                .map(|mark| Outcome::Win(Role::Mark(mark)))
+        } else if state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+
+    /// Hides every mark the viewer does not own unless a collision revealed it.
+    /// Spectators see only revealed marks; the whole board is shown once the game ends.
+    fn project(&self, state: &GameState, viewer: Option<Role>) -> GameState {
+        let mut view = state.clone();
+        if state.game_over {
+            return view;
+        }
+
+        // The move list would give away where hidden marks went, and the starting
+        // position of a game set up from notation would show the ones placed before.
+        view.history.clear();
+        view.undone.clear();
+        view.setup = state.setup.as_deref().and_then(|setup| {
+            let mut start = GameState::from_notation(String::new(), setup).ok()?;
+            Self::hide(&mut start.board, state, viewer);
+            start.to_notation().ok()
+        });
+
+        Self::hide(&mut view.board, state, viewer);
+        view
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    BoardConfig, CellState, Classic, GameError, GameState, Move, Outcome, Role, Ruleset, RulesetId,
+};
+
+/// The marks available to seats, in their default order.
+pub const MARKS: [CellState; 4] = [
+    CellState::X,
+    CellState::O,
+    CellState::Triangle,
+    CellState::Square,
+];
+
+/// K-in-a-row for three or four players, each with their own mark. The first
+/// line wins; if the board fills up, players are ranked by their longest run.
+pub struct FreeForAll;
+
+impl Ruleset for FreeForAll {
+    fn id(&self) -> RulesetId {
+        RulesetId::FreeForAll
+    }
+
+    fn default_config(&self) -> BoardConfig {
+        BoardConfig {
+            rows: 6,
+            cols: 6,
+            win_length: 3,
+        }
+    }
+
+    fn roles(&self) -> Vec<Role> {
+        MARKS[..3].iter().copied().map(Role::Mark).collect()
+    }
+
+    /// Three or four distinct marks in any order.
+    fn seats(&self, requested: Vec<Role>) -> Result<Vec<Role>, GameError> {
+        let valid = (3..=MARKS.len()).contains(&requested.len())
+            && requested.iter().enumerate().all(|(i, role)| {
+                role.mark().is_some_and(|mark| MARKS.contains(&mark))
+                    && !requested[..i].contains(role)
+            });
+        if !valid {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(requested)
+    }
+
+    fn has_board_symmetry(&self) -> bool {
+        true
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        Classic.legal_moves(state)
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        Classic.apply_move(state, mv)
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        Classic.outcome(state, last_move)
+    }
+
+    /// The winner, if any, comes first; everyone else is ranked by their longest run.
+    fn rankings(&self, state: &GameState, active: Vec<Role>) -> Vec<Vec<Role>> {
+        let (winner, mut rest): (Vec<Role>, Vec<Role>) = active
+            .into_iter()
+            .partition(|role| state.winner == Some(*role));
+        let run = |role: &Role| role.mark().map_or(0, |mark| state.board.longest_run(mark));
+        rest.sort_by_key(|role| std::cmp::Reverse(run(role)));
+
+        let mut places: Vec<Vec<Role>> = Vec::new();
+        for role in rest {
+            match places.last_mut() {
+                Some(place) if run(&place[0]) == run(&role) => place.push(role),
+                _ => places.push(vec![role]),
+            }
+        }
+        if !winner.is_empty() {
+            places.insert(0, winner);
+        }
+        places
+    }
+}
@@ -0,0 +1,95 @@
+use crate::ruleset::{only_marks, seat_marks};
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+
+/// Connect-Four style play: pieces are dropped into a column and fall to the bottom.
+pub struct Gravity;
+
+impl Gravity {
+    /// The lowest empty cell of `col`, where a dropped piece comes to rest.
+    pub fn landing_cell(state: &GameState, col: usize) -> Result<Coordinate, GameError> {
+        if col >= state.board.cols() {
+            return Err(GameError::OutOfBounds);
+        }
+
+        (0..state.board.rows())
+            .rev()
+            .filter_map(|row| Coordinate::new(row, col).ok())
+            .find(|cell| state.board.get(*cell) == Some(CellState::Empty))
+            .ok_or(GameError::ColumnFull)
+    }
+
+    /// The topmost piece of `col`, i.e. the one placed by the last drop into it.
+    fn top_piece(state: &GameState, col: usize) -> Option<Coordinate> {
+        (0..state.board.rows())
+            .filter_map(|row| Coordinate::new(row, col).ok())
+            .find(|cell| matches!(state.board.get(*cell), Some(mark) if mark != CellState::Empty))
+    }
+}
+
+impl Ruleset for Gravity {
+    fn id(&self) -> RulesetId {
+        RulesetId::Gravity
+    }
+
+    fn default_config(&self) -> BoardConfig {
+        BoardConfig {
+            rows: 6,
+            cols: 7,
+            win_length: 4,
+        }
+    }
+
+    /// Pieces must rest on the bottom row or on another piece.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        let floating = state.board.coordinates().any(|cell| {
+            let below = Coordinate::new(cell.row() + 1, cell.col())
+                .ok()
+                .and_then(|below| state.board.get(below));
+            state.board.get(cell) != Some(CellState::Empty) && below == Some(CellState::Empty)
+        });
+        if floating {
+            return Err(GameError::InvalidConfig);
+        }
+
+        only_marks(state, &seat_marks(state))?;
+        Ok(VariantState::None)
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        (0..state.board.cols())
+            .filter(|col| Self::landing_cell(state, *col).is_ok())
+            .map(Move::Drop)
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let Move::Drop(col) = mv else {
+            return Err(GameError::InvalidMove);
+        };
+
+        let cell = Self::landing_cell(state, col)?;
+        let mark = state.current_mark()?;
+        state.board.set(cell, mark)
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        let Move::Drop(col) = last_move else {
+            return None;
+        };
+
+        let cell = Self::top_piece(state, col)?;
+        if state.board.has_line_through(cell, state.config.win_length) {
+            state
+                .board
+                .get(cell)
+                .map(|mark| Outcome::Win(Role::Mark(mark)))
+        } else if state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::ruleset::empty_target;
+use crate::{GameError, GameState, Move, Outcome, Role, Ruleset, RulesetId};
+
+/// Classic geometry, but whoever completes a line loses.
+pub struct Misere;
+
+impl Ruleset for Misere {
+    fn id(&self) -> RulesetId {
+        RulesetId::Misere
+    }
+
+    fn has_board_symmetry(&self) -> bool {
+        true
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        state.board.empty_cells().map(Move::Place).collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let cell = empty_target(state, mv)?;
+        let mark = state.current_mark()?;
+        state.board.set(cell, mark)
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        let Move::Place(cell) = last_move else {
+            return None;
+        };
+
+        if state.board.has_line_through(cell, state.config.win_length) {
+            state
+                .board
+                .get(cell)
+                .map(|mark| Outcome::Win(Role::Mark(mark).opponent()))
+        } else if state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+}
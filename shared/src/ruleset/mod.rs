@@ -0,0 +1,344 @@
+pub mod classic;
+pub mod fog;
+pub mod free_for_all;
+pub mod gravity;
+pub mod misere;
+pub mod morris;
+pub mod notakto;
+pub mod numerical;
+pub mod order_chaos;
+pub mod quantum;
+pub mod qubic;
+pub mod ultimate;
+
+use crate::{Board, BoardConfig, CellState, Coordinate, GameError, GameState, Role};
+use serde::{Deserialize, Serialize};
+use std::mem;
+
+pub use classic::Classic;
+pub use fog::{Fog, FogState};
+pub use free_for_all::FreeForAll;
+pub use gravity::Gravity;
+pub use misere::Misere;
+pub use morris::{MorrisState, ThreeMensMorris};
+pub use notakto::{Notakto, NotaktoState};
+pub use numerical::{Numerical, NumericalState};
+pub use order_chaos::OrderChaos;
+pub use quantum::{Quantum, QuantumState, SpookyMark};
+pub use qubic::Qubic;
+pub use ultimate::{SubBoard, Ultimate, UltimateState};
+
+/// Names the rules a game is played under; chosen by the client in `CreateGame`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum RulesetId {
+    #[default]
+    Classic,
+    Ultimate,
+    Qubic,
+    Gravity,
+    Misere,
+    Notakto,
+    Quantum,
+    OrderChaos,
+    Numerical,
+    ThreeMensMorris,
+    Fog,
+    FreeForAll,
+}
+
+impl RulesetId {
+    pub fn ruleset(self) -> &'static dyn Ruleset {
+        match self {
+            RulesetId::Classic => &Classic,
+            RulesetId::Ultimate => &Ultimate,
+            RulesetId::Qubic => &Qubic,
+            RulesetId::Gravity => &Gravity,
+            RulesetId::Misere => &Misere,
+            RulesetId::Notakto => &Notakto,
+            RulesetId::Quantum => &Quantum,
+            RulesetId::OrderChaos => &OrderChaos,
+            RulesetId::Numerical => &Numerical,
+            RulesetId::ThreeMensMorris => &ThreeMensMorris,
+            RulesetId::Fog => &Fog,
+            RulesetId::FreeForAll => &FreeForAll,
+        }
+    }
+}
+
+/// Extra state a variant keeps alongside the board.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum VariantState {
+    #[default]
+    None,
+    Ultimate(UltimateState),
+    Notakto(NotaktoState),
+    Quantum(QuantumState),
+    Numerical(NumericalState),
+    Morris(MorrisState),
+    Fog(FogState),
+}
+
+impl VariantState {
+    /// Whether this bookkeeping has the shape of `expected`, a fresh state for the
+    /// same ruleset, and only refers to cells on `board`, so rulesets can index
+    /// into it without checking.
+    pub(crate) fn fits(&self, expected: &VariantState, board: &Board) -> bool {
+        match (self, expected) {
+            (VariantState::Ultimate(state), VariantState::Ultimate(expected)) => {
+                state.sub_boards.len() == expected.sub_boards.len()
+                    && state.active_board.is_none_or(|sub_board| {
+                        sub_board.row() < ultimate::SUB_BOARD_SIZE
+                            && sub_board.col() < ultimate::SUB_BOARD_SIZE
+                    })
+            }
+            (VariantState::Notakto(state), VariantState::Notakto(expected)) => {
+                state.dead_boards.len() == expected.dead_boards.len()
+            }
+            (VariantState::Quantum(state), VariantState::Quantum(expected)) => {
+                state.subscripts.len() == expected.subscripts.len()
+                    && state
+                        .spooky_marks
+                        .iter()
+                        .chain(&state.pending_collapse)
+                        .all(|mark| mark.cells.iter().all(|cell| board.contains(*cell)))
+            }
+            (VariantState::Morris(state), VariantState::Morris(_)) => {
+                state.positions.iter().all(|(position, _)| {
+                    position.rows() == board.rows() && position.cols() == board.cols()
+                })
+            }
+            _ => mem::discriminant(self) == mem::discriminant(expected),
+        }
+    }
+}
+
+/// A single action taken by the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Move {
+    Place(Coordinate),
+    /// Drops a piece into a column; it falls to the lowest empty cell.
+    Drop(usize),
+    /// Places an entangled pair of spooky marks in two different cells.
+    Spooky(Coordinate, Coordinate),
+    /// Chooses the cell the mark that closed an entanglement cycle collapses into.
+    Collapse(Coordinate),
+    /// Places a mark or number chosen by the mover rather than one fixed by their role.
+    PlaceSymbol(Coordinate, CellState),
+    /// Moves one of the mover's pieces from the first cell to the second.
+    Step(Coordinate, Coordinate),
+}
+
+/// How a finished game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Win(Role),
+    Draw,
+}
+
+/// The rules of one game variant.
+///
+/// `GameState` owns the players and turn bookkeeping; a ruleset only decides
+/// which moves are legal, what they do to the board and when the game ends.
+pub trait Ruleset: Sync {
+    fn id(&self) -> RulesetId;
+
+    /// The board used when the client does not ask for one.
+    fn default_config(&self) -> BoardConfig {
+        BoardConfig::default()
+    }
+
+    /// Checks the requested board, adjusting it if the variant fixes its own geometry.
+    fn board_config(&self, requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        requested.validate()?;
+        Ok(requested)
+    }
+
+    fn initial_variant_state(&self, _config: &BoardConfig) -> VariantState {
+        VariantState::None
+    }
+
+    /// Checks a position set up directly on the board rather than played, and
+    /// rebuilds the variant bookkeeping it implies. By default every mark must
+    /// belong to a seat, and there is nothing to rebuild.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        only_marks(state, &seat_marks(state))?;
+        Ok(self.initial_variant_state(&state.config))
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move>;
+
+    /// Applies `mv` for `state.current_player`, rejecting it if it is not legal.
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError>;
+
+    /// The result of the game after `last_move` was applied, or `None` while it is still running.
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome>;
+
+    /// The default seats of the game in turn order; the first role moves first.
+    fn roles(&self) -> Vec<Role> {
+        vec![Role::Mark(CellState::X), Role::Mark(CellState::O)]
+    }
+
+    /// Checks a seat order requested at creation; by default it must be a
+    /// reordering of [`Ruleset::roles`].
+    fn seats(&self, requested: Vec<Role>) -> Result<Vec<Role>, GameError> {
+        let roles = self.roles();
+        let is_reordering =
+            requested.len() == roles.len() && roles.iter().all(|role| requested.contains(role));
+        if !is_reordering {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(requested)
+    }
+
+    /// The cells that decided a game just won by `last_move`. By default these are
+    /// the runs of at least the configured length anywhere on the board.
+    fn winning_lines(&self, state: &GameState, _last_move: Move) -> Vec<Vec<Coordinate>> {
+        state.board.runs(state.config.win_length)
+    }
+
+    /// The side to move after `last_move`; by default the turn passes to the next
+    /// seat that has not been eliminated.
+    fn next_player(&self, state: &GameState, _last_move: Move) -> Role {
+        state.next_seat(state.current_player)
+    }
+
+    /// Places the seats still in play once the game is over, best first, grouping
+    /// seats that share a place. By default the winner is first and everyone else
+    /// is tied behind them.
+    fn rankings(&self, state: &GameState, active: Vec<Role>) -> Vec<Vec<Role>> {
+        match state.winner {
+            Some(winner) => {
+                let rest: Vec<Role> = active.into_iter().filter(|role| *role != winner).collect();
+                [vec![winner], rest]
+                    .into_iter()
+                    .filter(|place| !place.is_empty())
+                    .collect()
+            }
+            None => vec![active],
+        }
+    }
+
+    /// Whether rotating or reflecting a square board leaves the rules unchanged,
+    /// letting searches treat symmetric positions as one.
+    fn has_board_symmetry(&self) -> bool {
+        false
+    }
+
+    /// Whether every game ends after finitely many moves, so an exhaustive search
+    /// of the position terminates. Rulesets whose positions can repeat say no.
+    fn is_finite_search(&self) -> bool {
+        true
+    }
+
+    /// What `viewer` may see of `state`, with `None` for spectators; by default
+    /// nothing is hidden.
+    fn project(&self, state: &GameState, _viewer: Option<Role>) -> GameState {
+        state.clone()
+    }
+
+    fn is_terminal(&self, state: &GameState, last_move: Move) -> bool {
+        self.outcome(state, last_move).is_some()
+    }
+
+    /// Decides a position set up from notation, with `state.current_player` set to
+    /// the seat that moved last, returning the result and its winning lines.
+    ///
+    /// There is no last move to judge such a position by, so by default each
+    /// occupied cell is tried as the one just played there, and the first win
+    /// found beats any draw.
+    fn judge(&self, state: &GameState) -> Option<(Outcome, Vec<Vec<Coordinate>>)> {
+        let mut draw = None;
+        for cell in state.board.coordinates() {
+            let Some(mark) = state
+                .board
+                .get(cell)
+                .filter(|mark| *mark != CellState::Empty)
+            else {
+                continue;
+            };
+            for mv in [
+                Move::Place(cell),
+                Move::PlaceSymbol(cell, mark),
+                Move::Drop(cell.col()),
+            ] {
+                match self.outcome(state, mv) {
+                    Some(Outcome::Win(winner)) => {
+                        return Some((Outcome::Win(winner), self.winning_lines(state, mv)));
+                    }
+                    Some(Outcome::Draw) => draw = Some((Outcome::Draw, Vec::new())),
+                    None => {}
+                }
+            }
+        }
+        draw
+    }
+}
+
+/// The cell a `Move::Place` targets, provided it is on the board and still empty.
+pub(crate) fn empty_target(state: &GameState, mv: Move) -> Result<Coordinate, GameError> {
+    let Move::Place(cell) = mv else {
+        return Err(GameError::InvalidMove);
+    };
+
+    match state.board.get(cell) {
+        None => Err(GameError::OutOfBounds),
+        Some(CellState::Empty) => Ok(cell),
+        Some(_) => Err(GameError::CellOccupied),
+    }
+}
+
+/// Every three-in-a-row on a 3×3 grid, as (row, col) pairs.
+pub(crate) const LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
+
+/// The mark owning a complete line on a 3×3 grid, reading marks through `mark_at`.
+pub(crate) fn line_winner(
+    mark_at: impl Fn(usize, usize) -> Option<CellState>,
+) -> Option<CellState> {
+    LINES.iter().find_map(|line| {
+        let [a, b, c] = line.map(|(row, col)| mark_at(row, col));
+        a.filter(|mark| Some(*mark) == b && b == c)
+    })
+}
+
+/// The marks the seats of `state` place.
+pub(crate) fn seat_marks(state: &GameState) -> Vec<CellState> {
+    state.seats.iter().filter_map(|role| role.mark()).collect()
+}
+
+/// Fails unless every occupied cell of the board holds one of `marks`.
+pub(crate) fn only_marks(state: &GameState, marks: &[CellState]) -> Result<(), GameError> {
+    let valid = state
+        .board
+        .iter_rows()
+        .flatten()
+        .all(|cell| *cell == CellState::Empty || marks.contains(cell));
+    if !valid {
+        return Err(GameError::InvalidConfig);
+    }
+
+    Ok(())
+}
+
+/// Every line of a 3×3 grid held entirely by one mark, reading marks through `mark_at`.
+pub(crate) fn complete_lines(
+    mark_at: impl Fn(usize, usize) -> Option<CellState>,
+) -> Vec<[(usize, usize); 3]> {
+    LINES
+        .iter()
+        .copied()
+        .filter(|line| {
+            let [a, b, c] = line.map(|(row, col)| mark_at(row, col));
+            a.is_some() && a == b && b == c
+        })
+        .collect()
+}
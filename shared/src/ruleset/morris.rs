@@ -0,0 +1,179 @@
+use crate::ruleset::{empty_target, line_winner, only_marks};
+use crate::{
+    Board, BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+use serde::{Deserialize, Serialize};
+
+/// Pieces each player brings to the board.
+pub const PIECES: usize = 3;
+
+/// How often the same position must recur before the game is drawn.
+pub const REPETITION_LIMIT: usize = 3;
+
+/// Every position reached after a move, with the side to move in it, for repetition checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MorrisState {
+    pub positions: Vec<(Board, Role)>,
+}
+
+/// Whether a piece may step from `from` to `to`: one cell along a row or column,
+/// or diagonally to or from the centre point.
+pub fn adjacent(from: Coordinate, to: Coordinate) -> bool {
+    let rows = from.row().abs_diff(to.row());
+    let cols = from.col().abs_diff(to.col());
+    let touches_centre = [from, to]
+        .iter()
+        .any(|cell| cell.row() == 1 && cell.col() == 1);
+
+    match (rows, cols) {
+        (0, 1) | (1, 0) => true,
+        (1, 1) => touches_centre,
+        _ => false,
+    }
+}
+
+/// Pieces `mark` still has to place before the movement phase.
+pub fn pieces_in_hand(state: &GameState, mark: CellState) -> usize {
+    let placed = state
+        .board
+        .coordinates()
+        .filter(|cell| state.board.get(*cell) == Some(mark))
+        .count();
+    PIECES.saturating_sub(placed)
+}
+
+/// Three Men's Morris: each player places three pieces, then moves one of them
+/// to an adjacent empty point each turn until someone forms a line.
+pub struct ThreeMensMorris;
+
+impl ThreeMensMorris {
+    fn moves_for(state: &GameState, mark: CellState) -> Vec<Move> {
+        if pieces_in_hand(state, mark) > 0 {
+            return state.board.empty_cells().map(Move::Place).collect();
+        }
+
+        let pieces: Vec<Coordinate> = state
+            .board
+            .coordinates()
+            .filter(|cell| state.board.get(*cell) == Some(mark))
+            .collect();
+        pieces
+            .iter()
+            .flat_map(|from| {
+                state
+                    .board
+                    .empty_cells()
+                    .filter(|to| adjacent(*from, *to))
+                    .map(|to| Move::Step(*from, to))
+            })
+            .collect()
+    }
+}
+
+impl Ruleset for ThreeMensMorris {
+    fn id(&self) -> RulesetId {
+        RulesetId::ThreeMensMorris
+    }
+
+    fn board_config(&self, _requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        Ok(BoardConfig::default())
+    }
+
+    fn initial_variant_state(&self, _config: &BoardConfig) -> VariantState {
+        VariantState::Morris(MorrisState::default())
+    }
+
+    /// Pieces slide back and forth, so play can go on until a position repeats.
+    fn is_finite_search(&self) -> bool {
+        false
+    }
+
+    /// Nobody may have more than [`PIECES`] pieces on the board.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        only_marks(state, &[CellState::X, CellState::O])?;
+        let overfull = [CellState::X, CellState::O].iter().any(|mark| {
+            state
+                .board
+                .iter_rows()
+                .flatten()
+                .filter(|cell| *cell == mark)
+                .count()
+                > PIECES
+        });
+        if overfull {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(VariantState::Morris(MorrisState::default()))
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        state
+            .current_mark()
+            .map(|mark| Self::moves_for(state, mark))
+            .unwrap_or_default()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let mark = state.current_mark()?;
+        let placing = pieces_in_hand(state, mark) > 0;
+
+        match mv {
+            Move::Place(_) if placing => {
+                let cell = empty_target(state, mv)?;
+                state.board.set(cell, mark)?;
+            }
+            Move::Step(from, to) if !placing => {
+                if state.board.get(from).ok_or(GameError::OutOfBounds)? != mark {
+                    return Err(GameError::NotYourPiece);
+                }
+                let to = empty_target(state, Move::Place(to))?;
+                if !adjacent(from, to) {
+                    return Err(GameError::NotAdjacent);
+                }
+                state.board.set(from, CellState::Empty)?;
+                state.board.set(to, mark)?;
+            }
+            _ => return Err(GameError::InvalidMove),
+        }
+
+        let position = (state.board.clone(), state.current_player.opponent());
+        if let VariantState::Morris(morris) = &mut state.variant {
+            morris.positions.push(position);
+        }
+        Ok(())
+    }
+
+    /// A line wins; a player left without a legal move loses; a position seen
+    /// [`REPETITION_LIMIT`] times is a draw.
+    fn outcome(&self, state: &GameState, _last_move: Move) -> Option<Outcome> {
+        if let Some(mark) = line_winner(|row, col| {
+            Coordinate::new(row, col)
+                .ok()
+                .and_then(|cell| state.board.get(cell))
+                .filter(|mark| *mark != CellState::Empty)
+        }) {
+            return Some(Outcome::Win(Role::Mark(mark)));
+        }
+
+        let opponent = state.current_player.opponent();
+        if opponent
+            .mark()
+            .is_some_and(|mark| Self::moves_for(state, mark).is_empty())
+        {
+            return Some(Outcome::Win(state.current_player));
+        }
+
+        let VariantState::Morris(morris) = &state.variant else {
+            return None;
+        };
+        let last = morris.positions.last()?;
+        let seen = morris
+            .positions
+            .iter()
+            .filter(|position| *position == last)
+            .count();
+        (seen >= REPETITION_LIMIT).then_some(Outcome::Draw)
+    }
+}
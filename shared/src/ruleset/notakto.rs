@@ -0,0 +1,148 @@
+use crate::ruleset::{complete_lines, empty_target, line_winner, only_marks};
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Ruleset, RulesetId,
+    VariantState, MAX_BOARD_SIZE,
+};
+use serde::{Deserialize, Serialize};
+
+/// Rows and columns of each Notakto board.
+pub const BOARD_SIZE: usize = 3;
+
+/// Which boards are still in play; a board dies once it contains a line.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NotaktoState {
+    pub dead_boards: Vec<bool>,
+}
+
+/// The board containing `cell`; boards are laid out left to right.
+pub fn board_of(cell: Coordinate) -> usize {
+    cell.col() / BOARD_SIZE
+}
+
+/// Whether the `board`-th 3×3 board contains a line.
+fn has_line(state: &GameState, board: usize) -> bool {
+    line_winner(|row, col| {
+        Coordinate::new(row, board * BOARD_SIZE + col)
+            .ok()
+            .and_then(|cell| state.board.get(cell))
+            .filter(|mark| *mark != CellState::Empty)
+    })
+    .is_some()
+}
+
+/// Notakto: both players place X on one or more 3×3 boards, and whoever completes
+/// a line on the last live board loses.
+pub struct Notakto;
+
+impl Notakto {
+    fn state(state: &GameState) -> Option<&NotaktoState> {
+        match &state.variant {
+            VariantState::Notakto(notakto) => Some(notakto),
+            _ => None,
+        }
+    }
+
+    fn is_live(state: &GameState, cell: Coordinate) -> bool {
+        Self::state(state).is_some_and(|notakto| !notakto.dead_boards[board_of(cell)])
+    }
+}
+
+impl Ruleset for Notakto {
+    fn id(&self) -> RulesetId {
+        RulesetId::Notakto
+    }
+
+    fn default_config(&self) -> BoardConfig {
+        BoardConfig {
+            rows: BOARD_SIZE,
+            cols: BOARD_SIZE * 3,
+            win_length: BOARD_SIZE,
+        }
+    }
+
+    /// Keeps the requested number of boards (`cols / 3`), each a plain 3×3.
+    fn board_config(&self, requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        let boards = requested.cols / BOARD_SIZE;
+        if boards == 0 || boards * BOARD_SIZE > MAX_BOARD_SIZE {
+            return Err(GameError::InvalidConfig);
+        }
+
+        Ok(BoardConfig {
+            rows: BOARD_SIZE,
+            cols: boards * BOARD_SIZE,
+            win_length: BOARD_SIZE,
+        })
+    }
+
+    fn initial_variant_state(&self, config: &BoardConfig) -> VariantState {
+        VariantState::Notakto(NotaktoState {
+            dead_boards: vec![false; config.cols / BOARD_SIZE],
+        })
+    }
+
+    /// Only X is ever placed; boards holding a line are dead.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        only_marks(state, &[CellState::X])?;
+        Ok(VariantState::Notakto(NotaktoState {
+            dead_boards: (0..state.config.cols / BOARD_SIZE)
+                .map(|board| has_line(state, board))
+                .collect(),
+        }))
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        state
+            .board
+            .empty_cells()
+            .filter(|cell| Self::is_live(state, *cell))
+            .map(Move::Place)
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let cell = empty_target(state, mv)?;
+        if !Self::is_live(state, cell) {
+            return Err(GameError::BoardDead);
+        }
+
+        state.board.set(cell, CellState::X)?;
+
+        let board = board_of(cell);
+        let completed = has_line(state, board);
+        if let VariantState::Notakto(notakto) = &mut state.variant {
+            notakto.dead_boards[board] |= completed;
+        }
+
+        Ok(())
+    }
+
+    fn outcome(&self, state: &GameState, _last_move: Move) -> Option<Outcome> {
+        Self::state(state)?
+            .dead_boards
+            .iter()
+            .all(|dead| *dead)
+            .then(|| Outcome::Win(state.current_player.opponent()))
+    }
+
+    /// The line the loser completed on the last live board.
+    fn winning_lines(&self, state: &GameState, last_move: Move) -> Vec<Vec<Coordinate>> {
+        let Move::Place(cell) = last_move else {
+            return Vec::new();
+        };
+
+        let offset = board_of(cell) * BOARD_SIZE;
+        complete_lines(|row, col| {
+            Coordinate::new(row, offset + col)
+                .ok()
+                .and_then(|cell| state.board.get(cell))
+                .filter(|mark| *mark != CellState::Empty)
+        })
+        .into_iter()
+        .map(|line| {
+            line.iter()
+                .filter_map(|&(row, col)| Coordinate::new(row, offset + col).ok())
+                .collect()
+        })
+        .collect()
+    }
+}
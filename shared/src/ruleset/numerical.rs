@@ -0,0 +1,180 @@
+use crate::ruleset::LINES;
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+use serde::{Deserialize, Serialize};
+
+/// The total a complete line must add up to.
+pub const TARGET_SUM: u8 = 15;
+
+/// The numbers each side still holds; every number from 1 to 9 is played at most once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NumericalState {
+    pub odd: Vec<u8>,
+    pub even: Vec<u8>,
+}
+
+impl Default for NumericalState {
+    fn default() -> Self {
+        Self {
+            odd: vec![1, 3, 5, 7, 9],
+            even: vec![2, 4, 6, 8],
+        }
+    }
+}
+
+impl NumericalState {
+    /// The numbers `role` has left to play.
+    pub fn remaining(&self, role: Role) -> &[u8] {
+        match role {
+            Role::Odd => &self.odd,
+            Role::Even => &self.even,
+            _ => &[],
+        }
+    }
+
+    fn remaining_mut(&mut self, role: Role) -> Option<&mut Vec<u8>> {
+        match role {
+            Role::Odd => Some(&mut self.odd),
+            Role::Even => Some(&mut self.even),
+            _ => None,
+        }
+    }
+}
+
+/// Numerical tic-tac-toe: one side places odd numbers, the other even, and the
+/// first player to complete a line of three numbers summing to 15 wins.
+pub struct Numerical;
+
+impl Numerical {
+    fn state(state: &GameState) -> Option<&NumericalState> {
+        match &state.variant {
+            VariantState::Numerical(numerical) => Some(numerical),
+            _ => None,
+        }
+    }
+}
+
+impl Ruleset for Numerical {
+    fn id(&self) -> RulesetId {
+        RulesetId::Numerical
+    }
+
+    fn board_config(&self, _requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        Ok(BoardConfig::default())
+    }
+
+    fn initial_variant_state(&self, _config: &BoardConfig) -> VariantState {
+        VariantState::Numerical(NumericalState::default())
+    }
+
+    fn roles(&self) -> Vec<Role> {
+        vec![Role::Odd, Role::Even]
+    }
+
+    /// Each number may appear once; whatever is not on the board is still in hand.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        let mut numerical = NumericalState::default();
+        for cell in state.board.iter_rows().flatten() {
+            let number = match cell {
+                CellState::Empty => continue,
+                CellState::Number(number) => *number,
+                _ => return Err(GameError::InvalidConfig),
+            };
+            let remaining = if number % 2 == 1 {
+                &mut numerical.odd
+            } else {
+                &mut numerical.even
+            };
+            let index = remaining
+                .iter()
+                .position(|left| *left == number)
+                .ok_or(GameError::InvalidConfig)?;
+            remaining.remove(index);
+        }
+        Ok(VariantState::Numerical(numerical))
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        let Some(numerical) = Self::state(state) else {
+            return Vec::new();
+        };
+        let remaining = numerical.remaining(state.current_player);
+
+        state
+            .board
+            .empty_cells()
+            .flat_map(|cell| {
+                remaining
+                    .iter()
+                    .map(move |number| Move::PlaceSymbol(cell, CellState::Number(*number)))
+            })
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let Move::PlaceSymbol(cell, CellState::Number(number)) = mv else {
+            return Err(GameError::InvalidMove);
+        };
+        match state.board.get(cell) {
+            None => return Err(GameError::OutOfBounds),
+            Some(CellState::Empty) => {}
+            Some(_) => return Err(GameError::CellOccupied),
+        }
+
+        let role = state.current_player;
+        let VariantState::Numerical(numerical) = &mut state.variant else {
+            return Err(GameError::InvalidMove);
+        };
+        let remaining = numerical
+            .remaining_mut(role)
+            .ok_or(GameError::InvalidMove)?;
+        let index = remaining
+            .iter()
+            .position(|left| *left == number)
+            .ok_or(GameError::NumberUnavailable)?;
+        remaining.remove(index);
+
+        state.board.set(cell, CellState::Number(number))
+    }
+
+    fn outcome(&self, state: &GameState, _last_move: Move) -> Option<Outcome> {
+        if LINES.iter().any(|line| adds_up(state, line)) {
+            Some(Outcome::Win(state.current_player))
+        } else if state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn winning_lines(&self, state: &GameState, _last_move: Move) -> Vec<Vec<Coordinate>> {
+        LINES
+            .iter()
+            .filter(|line| adds_up(state, line))
+            .map(|line| {
+                line.iter()
+                    .filter_map(|&(row, col)| Coordinate::new(row, col).ok())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Whether every cell of `line` holds a number and they sum to [`TARGET_SUM`].
+fn adds_up(state: &GameState, line: &[(usize, usize); 3]) -> bool {
+    let numbers = line.map(|(row, col)| {
+        Coordinate::new(row, col)
+            .ok()
+            .and_then(|cell| state.board.get(cell))
+    });
+    let mut sum = 0;
+    for number in numbers {
+        let Some(CellState::Number(number)) = number else {
+            return false;
+        };
+        sum += number;
+    }
+    sum == TARGET_SUM
+}
@@ -0,0 +1,91 @@
+use crate::ruleset::only_marks;
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+
+/// Order and Chaos: both players may place either mark; Order wins with a line of
+/// five like marks, Chaos wins if the board fills up without one.
+pub struct OrderChaos;
+
+impl Ruleset for OrderChaos {
+    fn id(&self) -> RulesetId {
+        RulesetId::OrderChaos
+    }
+
+    fn default_config(&self) -> BoardConfig {
+        BoardConfig {
+            rows: 6,
+            cols: 6,
+            win_length: 5,
+        }
+    }
+
+    fn board_config(&self, _requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        Ok(self.default_config())
+    }
+
+    fn roles(&self) -> Vec<Role> {
+        vec![Role::Order, Role::Chaos]
+    }
+
+    fn has_board_symmetry(&self) -> bool {
+        true
+    }
+
+    /// Either side may have placed either mark.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        only_marks(state, &[CellState::X, CellState::O])?;
+        Ok(VariantState::None)
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        state
+            .board
+            .empty_cells()
+            .flat_map(|cell| [CellState::X, CellState::O].map(|mark| Move::PlaceSymbol(cell, mark)))
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let Move::PlaceSymbol(cell, mark) = mv else {
+            return Err(GameError::InvalidMove);
+        };
+        if !matches!(mark, CellState::X | CellState::O) {
+            return Err(GameError::InvalidMove);
+        }
+
+        match state.board.get(cell) {
+            None => Err(GameError::OutOfBounds),
+            Some(CellState::Empty) => state.board.set(cell, mark),
+            Some(_) => Err(GameError::CellOccupied),
+        }
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        let Move::PlaceSymbol(cell, _) = last_move else {
+            return None;
+        };
+
+        if state.board.has_line_through(cell, state.config.win_length) {
+            Some(Outcome::Win(Role::Order))
+        } else if state.board.is_full() {
+            Some(Outcome::Win(Role::Chaos))
+        } else {
+            None
+        }
+    }
+
+    /// A line anywhere wins for Order even on a full board; Chaos only wins a full
+    /// board without one.
+    fn judge(&self, state: &GameState) -> Option<(Outcome, Vec<Vec<Coordinate>>)> {
+        let runs = state.board.runs(state.config.win_length);
+        if !runs.is_empty() {
+            Some((Outcome::Win(Role::Order), runs))
+        } else if state.board.is_full() {
+            Some((Outcome::Win(Role::Chaos), Vec::new()))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use crate::ruleset::LINES;
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+use serde::{Deserialize, Serialize};
+
+/// Rows and columns of the quantum board.
+pub const BOARD_SIZE: usize = 3;
+
+/// One move's pair of entangled marks, not yet collapsed into a single cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpookyMark {
+    pub player: CellState,
+    /// The move number the mark was placed on, shown as its subscript.
+    pub turn: u8,
+    pub cells: [Coordinate; 2],
+}
+
+impl SpookyMark {
+    fn other_cell(&self, cell: Coordinate) -> Coordinate {
+        if self.cells[0] == cell {
+            self.cells[1]
+        } else {
+            self.cells[0]
+        }
+    }
+}
+
+/// The entanglement graph: cells are nodes and uncollapsed spooky marks are edges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QuantumState {
+    pub spooky_marks: Vec<SpookyMark>,
+    /// Subscript of the classical mark in each cell, row by row.
+    pub subscripts: Vec<Option<u8>>,
+    /// The mark that closed a cycle and is waiting for the opponent to collapse it.
+    pub pending_collapse: Option<SpookyMark>,
+    /// Number of the next move.
+    pub turn: u8,
+    /// Final score in half points, X first then O.
+    pub half_points: [u8; 2],
+}
+
+impl Default for QuantumState {
+    fn default() -> Self {
+        Self {
+            spooky_marks: Vec::new(),
+            subscripts: vec![None; BOARD_SIZE * BOARD_SIZE],
+            pending_collapse: None,
+            turn: 1,
+            half_points: [0, 0],
+        }
+    }
+}
+
+impl QuantumState {
+    /// Whether `a` and `b` are already joined through spooky marks.
+    fn connected(&self, a: Coordinate, b: Coordinate) -> bool {
+        let mut reached = vec![a];
+        let mut frontier = vec![a];
+        while let Some(cell) = frontier.pop() {
+            for mark in self
+                .spooky_marks
+                .iter()
+                .filter(|mark| mark.cells.contains(&cell))
+            {
+                let next = mark.other_cell(cell);
+                if next == b {
+                    return true;
+                }
+                if !reached.contains(&next) {
+                    reached.push(next);
+                    frontier.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    fn score_slot(player: CellState) -> usize {
+        if player == CellState::O {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+fn cell_index(cell: Coordinate) -> usize {
+    cell.row() * BOARD_SIZE + cell.col()
+}
+
+/// Quantum tic-tac-toe: every move puts a mark in superposition over two cells,
+/// and closing an entanglement cycle lets the opponent decide how it collapses.
+pub struct Quantum;
+
+impl Quantum {
+    fn state(state: &GameState) -> Option<&QuantumState> {
+        match &state.variant {
+            VariantState::Quantum(quantum) => Some(quantum),
+            _ => None,
+        }
+    }
+
+    fn free_cells(state: &GameState) -> Vec<Coordinate> {
+        state.board.empty_cells().collect()
+    }
+
+    /// Collapses `mark` into `cell`, forcing every mark entangled with it into its other cell.
+    fn collapse(
+        state: &mut GameState,
+        mark: SpookyMark,
+        cell: Coordinate,
+    ) -> Result<(), GameError> {
+        let VariantState::Quantum(quantum) = &mut state.variant else {
+            return Err(GameError::InvalidMove);
+        };
+
+        let mut forced = vec![(mark, cell)];
+        while let Some((mark, cell)) = forced.pop() {
+            quantum.spooky_marks.retain(|other| *other != mark);
+            state.board.set(cell, mark.player)?;
+            quantum.subscripts[cell_index(cell)] = Some(mark.turn);
+
+            let (touching, rest) = quantum
+                .spooky_marks
+                .iter()
+                .partition::<Vec<SpookyMark>, _>(|other| other.cells.contains(&cell));
+            quantum.spooky_marks = rest;
+            forced.extend(
+                touching
+                    .into_iter()
+                    .map(|other| (other, other.other_cell(cell))),
+            );
+        }
+
+        Self::score(state);
+        Ok(())
+    }
+
+    /// Scores completed lines: a lone line is worth a point, and when both players
+    /// complete one at once the line with the lower highest subscript takes the point
+    /// and the other gets half.
+    fn score(state: &mut GameState) {
+        let board = &state.board;
+        let VariantState::Quantum(quantum) = &mut state.variant else {
+            return;
+        };
+
+        let mut best: [Option<u8>; 2] = [None, None];
+        for player in [CellState::X, CellState::O] {
+            for line in LINES {
+                let cells = line.map(|(row, col)| Coordinate::new(row, col).ok());
+                if cells
+                    .iter()
+                    .all(|cell| cell.and_then(|cell| board.get(cell)) == Some(player))
+                {
+                    let highest = cells
+                        .iter()
+                        .flatten()
+                        .filter_map(|cell| quantum.subscripts[cell_index(*cell)])
+                        .max()
+                        .unwrap_or(0);
+                    let slot = &mut best[QuantumState::score_slot(player)];
+                    *slot = Some(slot.map_or(highest, |current| current.min(highest)));
+                }
+            }
+        }
+
+        quantum.half_points = match best {
+            [Some(x), Some(o)] if x < o => [2, 1],
+            [Some(_), Some(_)] => [1, 2],
+            [Some(_), None] => [2, 0],
+            [None, Some(_)] => [0, 2],
+            [None, None] => [0, 0],
+        };
+    }
+}
+
+impl Ruleset for Quantum {
+    fn id(&self) -> RulesetId {
+        RulesetId::Quantum
+    }
+
+    fn board_config(&self, _requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        Ok(BoardConfig::default())
+    }
+
+    fn initial_variant_state(&self, _config: &BoardConfig) -> VariantState {
+        VariantState::Quantum(QuantumState::default())
+    }
+
+    /// Spooky marks and their subscripts cannot be read off the board.
+    fn set_up(&self, _state: &GameState) -> Result<VariantState, GameError> {
+        Err(GameError::InvalidConfig)
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        let Some(quantum) = Self::state(state) else {
+            return Vec::new();
+        };
+
+        if let Some(pending) = quantum.pending_collapse {
+            return pending.cells.iter().copied().map(Move::Collapse).collect();
+        }
+
+        let free = Self::free_cells(state);
+        if let [last] = free[..] {
+            return vec![Move::Place(last)];
+        }
+
+        free.iter()
+            .enumerate()
+            .flat_map(|(i, first)| {
+                free[i + 1..]
+                    .iter()
+                    .map(|second| Move::Spooky(*first, *second))
+            })
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let quantum = Self::state(state).ok_or(GameError::InvalidMove)?;
+        let player = state.current_mark()?;
+        let turn = quantum.turn;
+
+        match (mv, quantum.pending_collapse) {
+            (Move::Collapse(cell), Some(pending)) => {
+                if !pending.cells.contains(&cell) {
+                    return Err(GameError::InvalidMove);
+                }
+                if let VariantState::Quantum(quantum) = &mut state.variant {
+                    quantum.pending_collapse = None;
+                }
+                Self::collapse(state, pending, cell)
+            }
+            (_, Some(_)) => Err(GameError::CollapsePending),
+            (Move::Spooky(first, second), None) => {
+                for cell in [first, second] {
+                    match state.board.get(cell) {
+                        None => return Err(GameError::OutOfBounds),
+                        Some(CellState::Empty) => {}
+                        Some(_) => return Err(GameError::CellOccupied),
+                    }
+                }
+                if first == second {
+                    return Err(GameError::InvalidMove);
+                }
+
+                let mark = SpookyMark {
+                    player,
+                    turn,
+                    cells: [first, second],
+                };
+                if let VariantState::Quantum(quantum) = &mut state.variant {
+                    if quantum.connected(first, second) {
+                        quantum.pending_collapse = Some(mark);
+                    }
+                    quantum.spooky_marks.push(mark);
+                    quantum.turn += 1;
+                }
+                Ok(())
+            }
+            (Move::Place(cell), None) => {
+                if Self::free_cells(state) != [cell] {
+                    return Err(GameError::InvalidMove);
+                }
+                state.board.set(cell, player)?;
+                if let VariantState::Quantum(quantum) = &mut state.variant {
+                    quantum.subscripts[cell_index(cell)] = Some(turn);
+                    quantum.turn += 1;
+                }
+                Self::score(state);
+                Ok(())
+            }
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+
+    fn outcome(&self, state: &GameState, _last_move: Move) -> Option<Outcome> {
+        let quantum = Self::state(state)?;
+        let [x, o] = quantum.half_points;
+        if x > o {
+            Some(Outcome::Win(Role::Mark(CellState::X)))
+        } else if o > x {
+            Some(Outcome::Win(Role::Mark(CellState::O)))
+        } else if quantum.pending_collapse.is_none() && state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+
+    /// Whoever collapses a cycle goes on to place their own marks in the same turn.
+    fn next_player(&self, state: &GameState, last_move: Move) -> Role {
+        match last_move {
+            Move::Collapse(_) => state.current_player,
+            _ => state.current_player.opponent(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::ruleset::empty_target;
+use crate::{
+    BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId,
+};
+use std::sync::OnceLock;
+
+/// Edge length of the cube.
+pub const SIZE: usize = 4;
+
+/// Cells per layer; layers are stacked top to bottom as rows of the flat board.
+const LAYER_CELLS: usize = SIZE * SIZE;
+
+/// Converts a (layer, row, col) position in the cube to its cell on the flat board.
+pub fn cell(layer: usize, row: usize, col: usize) -> Result<Coordinate, GameError> {
+    if layer >= SIZE || row >= SIZE || col >= SIZE {
+        return Err(GameError::OutOfBounds);
+    }
+
+    Coordinate::new(layer * SIZE + row, col)
+}
+
+/// Index of `cell` within the cube, as used by [`lines`].
+fn cube_index(cell: Coordinate) -> usize {
+    cell.row() * SIZE + cell.col()
+}
+
+/// The cell on the flat board at cube index `index`.
+fn board_cell(index: usize) -> Option<Coordinate> {
+    Coordinate::new(index / SIZE, index % SIZE).ok()
+}
+
+/// The lines through `cell` that its mark fills completely.
+fn lines_through(state: &GameState, cell: Coordinate) -> Vec<&'static [usize; SIZE]> {
+    let Some(mark) = state
+        .board
+        .get(cell)
+        .filter(|mark| *mark != CellState::Empty)
+    else {
+        return Vec::new();
+    };
+
+    let index = cube_index(cell);
+    lines()
+        .iter()
+        .filter(|line| line.contains(&index))
+        .filter(|line| {
+            line.iter().all(|index| {
+                board_cell(*index).and_then(|cell| state.board.get(cell)) == Some(mark)
+            })
+        })
+        .collect()
+}
+
+/// All 76 winning lines of the cube, as cube indices.
+pub fn lines() -> &'static [[usize; SIZE]] {
+    static LINES: OnceLock<Vec<[usize; SIZE]>> = OnceLock::new();
+    LINES.get_or_init(|| {
+        let mut lines = Vec::new();
+        // One direction out of each opposite pair: the first non-zero step is positive.
+        for dz in -1isize..=1 {
+            for dy in -1isize..=1 {
+                for dx in -1isize..=1 {
+                    let first = [dz, dy, dx].into_iter().find(|step| *step != 0);
+                    if first != Some(1) {
+                        continue;
+                    }
+                    for start in 0..LAYER_CELLS * SIZE {
+                        let origin = [start / LAYER_CELLS, start / SIZE % SIZE, start % SIZE];
+                        if let Some(line) = walk(origin, [dz, dy, dx]) {
+                            lines.push(line);
+                        }
+                    }
+                }
+            }
+        }
+        lines
+    })
+}
+
+fn walk(origin: [usize; 3], step: [isize; 3]) -> Option<[usize; SIZE]> {
+    let mut line = [0; SIZE];
+    for (distance, slot) in line.iter_mut().enumerate() {
+        let mut position = [0; 3];
+        for axis in 0..3 {
+            let value = origin[axis].checked_add_signed(step[axis] * distance as isize)?;
+            if value >= SIZE {
+                return None;
+            }
+            position[axis] = value;
+        }
+        *slot = position[0] * LAYER_CELLS + position[1] * SIZE + position[2];
+    }
+    Some(line)
+}
+
+/// Qubic: four in a row anywhere in a 4×4×4 cube, including across layers.
+pub struct Qubic;
+
+impl Ruleset for Qubic {
+    fn id(&self) -> RulesetId {
+        RulesetId::Qubic
+    }
+
+    fn board_config(&self, _requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        Ok(BoardConfig {
+            rows: SIZE * SIZE,
+            cols: SIZE,
+            win_length: SIZE,
+        })
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        state.board.empty_cells().map(Move::Place).collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let cell = empty_target(state, mv)?;
+        let mark = state.current_mark()?;
+        state.board.set(cell, mark)
+    }
+
+    fn outcome(&self, state: &GameState, last_move: Move) -> Option<Outcome> {
+        let Move::Place(cell) = last_move else {
+            return None;
+        };
+
+        if !lines_through(state, cell).is_empty() {
+            state
+                .board
+                .get(cell)
+                .map(|mark| Outcome::Win(Role::Mark(mark)))
+        } else if state.board.is_full() {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn winning_lines(&self, state: &GameState, last_move: Move) -> Vec<Vec<Coordinate>> {
+        let Move::Place(cell) = last_move else {
+            return Vec::new();
+        };
+
+        lines_through(state, cell)
+            .into_iter()
+            .map(|line| line.iter().filter_map(|index| board_cell(*index)).collect())
+            .collect()
+    }
+}
@@ -0,0 +1,217 @@
+use crate::ruleset::{complete_lines, empty_target, line_winner, only_marks};
+use crate::{
+    Board, BoardConfig, CellState, Coordinate, GameError, GameState, Move, Outcome, Role, Ruleset,
+    RulesetId, VariantState,
+};
+use serde::{Deserialize, Serialize};
+
+/// Rows and columns of a sub-board, and of the grid of sub-boards.
+pub const SUB_BOARD_SIZE: usize = 3;
+
+/// Whether a sub-board can still be played in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SubBoard {
+    Open,
+    Won(CellState),
+    Drawn,
+}
+
+/// Nine small boards, and where the next move has to go.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UltimateState {
+    /// The sub-board the side to move is sent to, or `None` when any open one may be used.
+    pub active_board: Option<Coordinate>,
+    /// Status of each sub-board, row by row.
+    pub sub_boards: Vec<SubBoard>,
+}
+
+impl UltimateState {
+    pub fn sub_board(&self, board: Coordinate) -> SubBoard {
+        self.sub_boards[board.row() * SUB_BOARD_SIZE + board.col()]
+    }
+
+    fn is_playable(&self, board: Coordinate) -> bool {
+        self.sub_board(board) == SubBoard::Open
+            && self.active_board.is_none_or(|active| active == board)
+    }
+}
+
+impl Default for UltimateState {
+    fn default() -> Self {
+        Self {
+            active_board: None,
+            sub_boards: vec![SubBoard::Open; SUB_BOARD_SIZE * SUB_BOARD_SIZE],
+        }
+    }
+}
+
+/// Converts a cell addressed within a sub-board to its position on the full 9×9 board.
+pub fn global_cell(board: Coordinate, cell: Coordinate) -> Result<Coordinate, GameError> {
+    if [board.row(), board.col(), cell.row(), cell.col()]
+        .iter()
+        .any(|index| *index >= SUB_BOARD_SIZE)
+    {
+        return Err(GameError::OutOfBounds);
+    }
+
+    Coordinate::new(
+        board.row() * SUB_BOARD_SIZE + cell.row(),
+        board.col() * SUB_BOARD_SIZE + cell.col(),
+    )
+}
+
+/// The sub-board containing `cell`.
+pub fn sub_board_of(cell: Coordinate) -> Coordinate {
+    local_coordinate(cell.row() / SUB_BOARD_SIZE, cell.col() / SUB_BOARD_SIZE)
+}
+
+/// The position of `cell` within its sub-board, which is also the sub-board the opponent is sent to.
+pub fn local_cell(cell: Coordinate) -> Coordinate {
+    local_coordinate(cell.row() % SUB_BOARD_SIZE, cell.col() % SUB_BOARD_SIZE)
+}
+
+fn local_coordinate(row: usize, col: usize) -> Coordinate {
+    Coordinate::new(row, col).expect("sub-board coordinates are always in range")
+}
+
+fn sub_board_cells(board: Coordinate) -> impl Iterator<Item = Coordinate> {
+    (0..SUB_BOARD_SIZE).flat_map(move |row| {
+        (0..SUB_BOARD_SIZE)
+            .filter_map(move |col| global_cell(board, local_coordinate(row, col)).ok())
+    })
+}
+
+/// Whether `board` has been won, drawn or is still open on `grid`.
+fn sub_board_status(grid: &Board, board: Coordinate) -> SubBoard {
+    let won = line_winner(|row, col| {
+        global_cell(board, local_coordinate(row, col))
+            .ok()
+            .and_then(|cell| grid.get(cell))
+            .filter(|mark| *mark != CellState::Empty)
+    });
+    match won {
+        Some(mark) => SubBoard::Won(mark),
+        None if sub_board_cells(board).all(|cell| grid.get(cell) != Some(CellState::Empty)) => {
+            SubBoard::Drawn
+        }
+        None => SubBoard::Open,
+    }
+}
+
+/// Ultimate tic-tac-toe: win three sub-boards in a row, where each move picks the
+/// sub-board the opponent must play in next.
+pub struct Ultimate;
+
+impl Ultimate {
+    fn state(state: &GameState) -> Option<&UltimateState> {
+        match &state.variant {
+            VariantState::Ultimate(ultimate) => Some(ultimate),
+            _ => None,
+        }
+    }
+}
+
+impl Ruleset for Ultimate {
+    fn id(&self) -> RulesetId {
+        RulesetId::Ultimate
+    }
+
+    fn board_config(&self, _requested: BoardConfig) -> Result<BoardConfig, GameError> {
+        Ok(BoardConfig {
+            rows: SUB_BOARD_SIZE * SUB_BOARD_SIZE,
+            cols: SUB_BOARD_SIZE * SUB_BOARD_SIZE,
+            win_length: SUB_BOARD_SIZE,
+        })
+    }
+
+    fn initial_variant_state(&self, _config: &BoardConfig) -> VariantState {
+        VariantState::Ultimate(UltimateState::default())
+    }
+
+    /// Sub-board results follow from the board; any open sub-board may be played
+    /// until told otherwise.
+    fn set_up(&self, state: &GameState) -> Result<VariantState, GameError> {
+        only_marks(state, &[CellState::X, CellState::O])?;
+        let sub_boards = (0..SUB_BOARD_SIZE)
+            .flat_map(|row| (0..SUB_BOARD_SIZE).map(move |col| local_coordinate(row, col)))
+            .map(|board| sub_board_status(&state.board, board))
+            .collect();
+        Ok(VariantState::Ultimate(UltimateState {
+            active_board: None,
+            sub_boards,
+        }))
+    }
+
+    fn legal_moves(&self, state: &GameState) -> Vec<Move> {
+        let Some(ultimate) = Self::state(state) else {
+            return Vec::new();
+        };
+
+        state
+            .board
+            .empty_cells()
+            .filter(|cell| ultimate.is_playable(sub_board_of(*cell)))
+            .map(Move::Place)
+            .collect()
+    }
+
+    fn apply_move(&self, state: &mut GameState, mv: Move) -> Result<(), GameError> {
+        let cell = empty_target(state, mv)?;
+        let mark = state.current_mark()?;
+
+        let board = sub_board_of(cell);
+        let VariantState::Ultimate(ultimate) = &mut state.variant else {
+            return Err(GameError::InvalidMove);
+        };
+        if !ultimate.is_playable(board) {
+            return Err(GameError::WrongSubBoard);
+        }
+
+        state.board.set(cell, mark)?;
+
+        ultimate.sub_boards[board.row() * SUB_BOARD_SIZE + board.col()] =
+            sub_board_status(&state.board, board);
+
+        let next = local_cell(cell);
+        ultimate.active_board = (ultimate.sub_board(next) == SubBoard::Open).then_some(next);
+
+        Ok(())
+    }
+
+    fn outcome(&self, state: &GameState, _last_move: Move) -> Option<Outcome> {
+        let ultimate = Self::state(state)?;
+
+        let winner = line_winner(
+            |row, col| match ultimate.sub_board(local_coordinate(row, col)) {
+                SubBoard::Won(mark) => Some(mark),
+                _ => None,
+            },
+        );
+        if let Some(winner) = winner {
+            return Some(Outcome::Win(Role::Mark(winner)));
+        }
+
+        ultimate
+            .sub_boards
+            .iter()
+            .all(|status| *status != SubBoard::Open)
+            .then_some(Outcome::Draw)
+    }
+
+    /// The line of won sub-boards, given as sub-board coordinates.
+    fn winning_lines(&self, state: &GameState, _last_move: Move) -> Vec<Vec<Coordinate>> {
+        let Some(ultimate) = Self::state(state) else {
+            return Vec::new();
+        };
+
+        complete_lines(
+            |row, col| match ultimate.sub_board(local_coordinate(row, col)) {
+                SubBoard::Won(mark) => Some(mark),
+                _ => None,
+            },
+        )
+        .into_iter()
+        .map(|line| line.map(|(row, col)| local_coordinate(row, col)).to_vec())
+        .collect()
+    }
+}
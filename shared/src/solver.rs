@@ -0,0 +1,300 @@
+//! Perfect play by exhaustive negamax search with alpha-beta pruning.
+//!
+//! Positions are cached in a transposition table keyed by their canonical form,
+//! so positions that differ only by a rotation or reflection are solved once.
+//! Only two-sided games are meaningful: any win that is not the mover's counts
+//! as a loss for them. Games that can cycle, like Three Men's Morris, are only
+//! bounded by their repetition rule and are far too large to solve this way.
+//!
+//! Every search is capped at a number of visited positions and gives up with
+//! [`GameError::TooComplex`] once it runs out, so no position can stall the caller.
+
+use crate::{GameError, GameState, Move, Role, VariantState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Positions with more empty cells than this are not worth attempting; the
+/// search would run out of budget long before finishing.
+pub const MAX_EMPTY_CELLS: usize = 16;
+
+/// Positions a single search may visit before giving up, enough for any 3×3
+/// game and for the endgames of larger boards.
+pub const NODE_BUDGET: u64 = 200_000;
+
+/// Score of a win on the very next ply; every further ply costs one point, so
+/// quicker wins and slower losses score better.
+const WIN: i32 = 1_000_000;
+
+/// The game-theoretic value of a position for the side to move, with the number
+/// of plies until the game ends under best play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Value {
+    Win(u32),
+    Draw,
+    Loss(u32),
+}
+
+impl Value {
+    fn from_score(score: i32) -> Self {
+        if score > 0 {
+            Value::Win((WIN - score) as u32)
+        } else if score < 0 {
+            Value::Loss((WIN + score) as u32)
+        } else {
+            Value::Draw
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Win(plies) => write!(f, "Win in {plies}"),
+            Value::Draw => f.write_str("Draw"),
+            Value::Loss(plies) => write!(f, "Loss in {plies}"),
+        }
+    }
+}
+
+/// The value of a position and every move that achieves it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Solution {
+    pub value: Value,
+    pub best_moves: Vec<Move>,
+}
+
+#[derive(Clone, Copy)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+    score: i32,
+    bound: Bound,
+}
+
+/// A position's canonical hash, plus a hash of its variant bookkeeping for the
+/// rulesets that keep any, since that is not part of the Zobrist hash.
+#[derive(PartialEq, Eq, Hash)]
+struct Key {
+    hash: u64,
+    variant: u64,
+}
+
+/// A reusable search; keeping one around lets later searches reuse the table.
+pub struct Solver {
+    table: HashMap<Key, Entry>,
+    budget: u64,
+    nodes: u64,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::with_budget(NODE_BUDGET)
+    }
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A solver that gives up on any search visiting more than `budget` positions.
+    pub fn with_budget(budget: u64) -> Self {
+        Self {
+            table: HashMap::new(),
+            budget,
+            nodes: 0,
+        }
+    }
+
+    /// The value of `state` for the side to move and all of its best moves.
+    pub fn solve(&mut self, state: &GameState) -> Result<Solution, GameError> {
+        if state.game_over {
+            let value = match state.winner {
+                None => Value::Draw,
+                Some(winner) if winner == state.current_player => Value::Win(0),
+                Some(_) => Value::Loss(0),
+            };
+            return Ok(Solution {
+                value,
+                best_moves: Vec::new(),
+            });
+        }
+
+        let scores = self.score_moves(state)?;
+        let best = scores.iter().map(|(_, score)| *score).max().unwrap_or(0);
+        Ok(Solution {
+            value: Value::from_score(best),
+            best_moves: scores
+                .into_iter()
+                .filter(|(_, score)| *score == best)
+                .map(|(mv, _)| mv)
+                .collect(),
+        })
+    }
+
+    /// The exact value of every legal move in `state`, for the side to move.
+    pub fn analyze(&mut self, state: &GameState) -> Result<Vec<(Move, Value)>, GameError> {
+        Ok(self
+            .score_moves(state)?
+            .into_iter()
+            .map(|(mv, score)| (mv, Value::from_score(score)))
+            .collect())
+    }
+
+    fn score_moves(&mut self, state: &GameState) -> Result<Vec<(Move, i32)>, GameError> {
+        self.nodes = 0;
+        let scores = state
+            .legal_moves()
+            .into_iter()
+            .filter_map(|mv| {
+                let mut child = state.clone();
+                child.play(mv).ok()?;
+                let score = self.child_score(&child, state.current_player, 1, -WIN, WIN);
+                Some((mv, score))
+            })
+            .collect();
+        if self.out_of_budget() {
+            return Err(GameError::TooComplex);
+        }
+        Ok(scores)
+    }
+
+    fn out_of_budget(&self) -> bool {
+        self.nodes > self.budget
+    }
+
+    /// The score of `child` for `mover`, who just played into it at `ply`.
+    fn child_score(
+        &mut self,
+        child: &GameState,
+        mover: Role,
+        ply: i32,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        if child.game_over {
+            return match child.winner {
+                None => 0,
+                Some(winner) if winner == mover => WIN - ply,
+                Some(_) => ply - WIN,
+            };
+        }
+
+        // Some variants hand the mover another turn.
+        if child.current_player == mover {
+            self.negamax(child, ply, alpha, beta)
+        } else {
+            -self.negamax(child, ply, -beta, -alpha)
+        }
+    }
+
+    fn negamax(&mut self, state: &GameState, ply: i32, mut alpha: i32, mut beta: i32) -> i32 {
+        // Once the budget is spent every score is meaningless; unwind quickly.
+        self.nodes += 1;
+        if self.out_of_budget() {
+            return 0;
+        }
+
+        let key = position_key(state);
+        let original_alpha = alpha;
+        if let Some(entry) = self.table.get(&key) {
+            let score = from_table(entry.score, ply);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower => alpha = alpha.max(score),
+                Bound::Upper => beta = beta.min(score),
+            }
+            if alpha >= beta {
+                return score;
+            }
+        }
+
+        let mut best = None;
+        for mv in state.legal_moves() {
+            let mut child = state.clone();
+            if child.play(mv).is_err() {
+                continue;
+            }
+
+            let score = self.child_score(&child, state.current_player, ply + 1, alpha, beta);
+            best = Some(best.map_or(score, |best: i32| best.max(score)));
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        // A position with no legal moves left is a dead end for both sides.
+        let best = best.unwrap_or(0);
+        if self.out_of_budget() {
+            return best;
+        }
+        let bound = if best <= original_alpha {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.insert(
+            key,
+            Entry {
+                score: to_table(best, ply),
+                bound,
+            },
+        );
+        best
+    }
+}
+
+/// Whether `state` is worth handing to the solver: it is two-sided, cannot cycle,
+/// and has few enough empty cells left that the search has a chance within its budget.
+pub fn is_solvable(state: &GameState) -> bool {
+    state.seats.len() == 2
+        && state.ruleset.ruleset().is_finite_search()
+        && state.board.empty_cells().count() <= MAX_EMPTY_CELLS
+}
+
+/// Solves `state` with a fresh table.
+pub fn solve(state: &GameState) -> Result<Solution, GameError> {
+    Solver::new().solve(state)
+}
+
+/// Win and loss scores are stored relative to the cached position rather than
+/// the root, so an entry stays valid wherever the position is reached.
+fn to_table(score: i32, ply: i32) -> i32 {
+    match score {
+        s if s > 0 => s + ply,
+        s if s < 0 => s - ply,
+        _ => 0,
+    }
+}
+
+fn from_table(score: i32, ply: i32) -> i32 {
+    match score {
+        s if s > 0 => s - ply,
+        s if s < 0 => s + ply,
+        _ => 0,
+    }
+}
+
+fn position_key(state: &GameState) -> Key {
+    Key {
+        hash: state.canonical_hash(),
+        variant: match state.variant {
+            VariantState::None => 0,
+            ref variant => {
+                let mut hasher = DefaultHasher::new();
+                variant.hash(&mut hasher);
+                hasher.finish()
+            }
+        },
+    }
+}
@@ -0,0 +1,132 @@
+//! The eight rotations and reflections of a square board, and the canonical form
+//! that lets positions related by them be treated as one.
+
+use crate::{zobrist, Board, Coordinate, GameState, VariantState};
+use serde::{Deserialize, Serialize};
+
+/// One of the eight symmetries of a square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Where `cell` ends up on a `size`×`size` board; `cell` must lie on that board.
+    pub fn apply(self, cell: Coordinate, size: usize) -> Coordinate {
+        let (row, col) = (cell.row(), cell.col());
+        let last = size - 1;
+        let (row, col) = match self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, last - row),
+            Symmetry::Rotate180 => (last - row, last - col),
+            Symmetry::Rotate270 => (last - col, row),
+            Symmetry::FlipHorizontal => (row, last - col),
+            Symmetry::FlipVertical => (last - row, col),
+            Symmetry::Transpose => (col, row),
+            Symmetry::AntiTranspose => (last - col, last - row),
+        };
+        Coordinate::new(row, col).unwrap_or(cell)
+    }
+
+    /// The symmetry that undoes this one.
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+}
+
+impl Board {
+    /// The board rotated or reflected by `symmetry`, or `None` if it is not square.
+    pub fn transformed(&self, symmetry: Symmetry) -> Option<Board> {
+        if self.rows() != self.cols() {
+            return None;
+        }
+
+        let mut board = Board::new(self.rows(), self.cols());
+        for cell in self.coordinates() {
+            let piece = self.get(cell)?;
+            board.set(symmetry.apply(cell, self.rows()), piece).ok()?;
+        }
+        Some(board)
+    }
+
+    /// The Zobrist hash the board would have after `symmetry`, without building it.
+    fn transformed_zobrist(&self, symmetry: Symmetry) -> u64 {
+        self.coordinates()
+            .filter_map(|cell| {
+                let piece = self.get(cell)?;
+                Some(zobrist::cell_key(symmetry.apply(cell, self.rows()), piece))
+            })
+            .fold(0, |hash, key| hash ^ key)
+    }
+}
+
+impl GameState {
+    /// Whether rotating or reflecting the board leaves this position's rules and
+    /// any variant bookkeeping unchanged.
+    pub fn is_symmetric(&self) -> bool {
+        self.board.rows() == self.board.cols()
+            && self.variant == VariantState::None
+            && self.ruleset.ruleset().has_board_symmetry()
+    }
+
+    /// The symmetry taking this board to its canonical form: of its eight images,
+    /// the one with the smallest Zobrist hash. Positions without symmetry are
+    /// already canonical.
+    pub fn canonical_symmetry(&self) -> Symmetry {
+        if !self.is_symmetric() {
+            return Symmetry::Identity;
+        }
+
+        Symmetry::ALL
+            .into_iter()
+            .min_by_key(|symmetry| self.board.transformed_zobrist(*symmetry))
+            .unwrap_or(Symmetry::Identity)
+    }
+
+    /// The board in canonical form, with the symmetry that produced it.
+    pub fn canonical(&self) -> (Board, Symmetry) {
+        let symmetry = self.canonical_symmetry();
+        let board = self
+            .board
+            .transformed(symmetry)
+            .unwrap_or_else(|| self.board.clone());
+        (board, symmetry)
+    }
+
+    /// Hash of the canonical form and the side to move; equal for every position
+    /// that is a rotation or reflection of this one.
+    pub fn canonical_hash(&self) -> u64 {
+        let board = if self.is_symmetric() {
+            Symmetry::ALL
+                .into_iter()
+                .map(|symmetry| self.board.transformed_zobrist(symmetry))
+                .min()
+                .unwrap_or_default()
+        } else {
+            self.board.zobrist()
+        };
+        board ^ zobrist::role_key(self.current_player)
+    }
+}
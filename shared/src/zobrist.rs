@@ -0,0 +1,74 @@
+//! Zobrist hashing: every (cell, piece) pair and every side to move has a fixed
+//! random key, and a position hashes to the XOR of the keys present in it. A
+//! move only toggles the keys of the cells it touches, so the hash is kept up
+//! to date as the board changes instead of being recomputed.
+
+use crate::{CellState, Coordinate, Role, MAX_BOARD_SIZE};
+use std::sync::OnceLock;
+
+/// X, O, triangle, square and the numbers 1 to 9.
+const PIECES: usize = 13;
+
+/// The four marks, Order and Chaos, and the odd and even sides.
+const ROLES: usize = 8;
+
+struct Keys {
+    cells: Vec<u64>,
+    roles: [u64; ROLES],
+}
+
+/// SplitMix64, seeded with a constant so hashes are stable across runs and machines.
+pub(crate) fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn keys() -> &'static Keys {
+    static KEYS: OnceLock<Keys> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let mut seed = 0x7469_632d_7461_632d;
+        Keys {
+            cells: (0..MAX_BOARD_SIZE * MAX_BOARD_SIZE * PIECES)
+                .map(|_| split_mix(&mut seed))
+                .collect(),
+            roles: std::array::from_fn(|_| split_mix(&mut seed)),
+        }
+    })
+}
+
+fn piece_index(piece: CellState) -> Option<usize> {
+    match piece {
+        CellState::Empty => None,
+        CellState::X => Some(0),
+        CellState::O => Some(1),
+        CellState::Triangle => Some(2),
+        CellState::Square => Some(3),
+        CellState::Number(number @ 1..=9) => Some(3 + number as usize),
+        CellState::Number(_) => None,
+    }
+}
+
+/// The key for `piece` standing on `cell`; empty cells contribute nothing.
+pub fn cell_key(cell: Coordinate, piece: CellState) -> u64 {
+    piece_index(piece).map_or(0, |piece| {
+        keys().cells[(cell.row() * MAX_BOARD_SIZE + cell.col()) * PIECES + piece]
+    })
+}
+
+/// The key for `role` being the side to move.
+pub fn role_key(role: Role) -> u64 {
+    let index = match role {
+        Role::Mark(CellState::O) => 1,
+        Role::Mark(CellState::Triangle) => 2,
+        Role::Mark(CellState::Square) => 3,
+        Role::Mark(_) => 0,
+        Role::Order => 4,
+        Role::Chaos => 5,
+        Role::Odd => 6,
+        Role::Even => 7,
+    };
+    keys().roles[index]
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{fresh, parse};
+use shared::analysis::analyze;
+use shared::solver::Value;
+use shared::{
+    Board, CellState, ClientMessage, GameError, GameState, Role, RulesetId, VariantState,
+};
+
+#[test]
+fn small_positions_are_analysed() {
+    let analysis = analyze(&parse("3x3:3 classic xx1/2o/1o1 x 5")).unwrap();
+    assert_eq!(analysis.value, Value::Win(1));
+    assert_eq!(analysis.principal_variation.len(), 1);
+}
+
+#[test]
+fn large_and_multi_seat_positions_are_refused() {
+    assert_eq!(
+        analyze(&fresh(RulesetId::Gravity)),
+        Err(GameError::TooComplex)
+    );
+    assert_eq!(
+        analyze(&parse("3x3:3 free-for-all 3/1x1/3 o 2 xot")),
+        Err(GameError::TooComplex)
+    );
+}
+
+#[test]
+fn boards_that_do_not_fill_their_size_are_refused() {
+    let mut position = serde_json::to_value(fresh(RulesetId::Classic)).unwrap();
+    position["board"]["cells"] = serde_json::json!(["X"]);
+    let message = serde_json::json!({ "Analyze": { "position": position } });
+
+    assert!(serde_json::from_value::<GameState>(position).is_err());
+    assert!(serde_json::from_value::<ClientMessage>(message).is_err());
+}
+
+#[test]
+fn boards_past_the_largest_size_are_refused() {
+    let huge = serde_json::json!({ "rows": 3_000_000_000u64, "cols": 0, "cells": [] });
+    assert!(serde_json::from_value::<Board>(huge.clone()).is_err());
+
+    let mut position = serde_json::to_value(fresh(RulesetId::Classic)).unwrap();
+    position["board"] = huge.clone();
+    let message = serde_json::json!({ "Analyze": { "position": position } });
+    assert!(serde_json::from_value::<ClientMessage>(message).is_err());
+
+    // Boards kept for the repetition rule are checked the same way.
+    let mut morris = serde_json::to_value(fresh(RulesetId::ThreeMensMorris)).unwrap();
+    morris["variant"]["Morris"]["positions"] = serde_json::json!([[huge, { "Mark": "X" }]]);
+    assert!(serde_json::from_value::<GameState>(morris).is_err());
+}
+
+#[test]
+fn variant_bookkeeping_of_the_wrong_size_is_refused() {
+    let mut ultimate = fresh(RulesetId::Ultimate);
+    if let VariantState::Ultimate(state) = &mut ultimate.variant {
+        state.sub_boards.truncate(1);
+    }
+
+    let mut notakto = fresh(RulesetId::Notakto);
+    if let VariantState::Notakto(state) = &mut notakto.variant {
+        state.dead_boards.clear();
+    }
+
+    let mut morris = fresh(RulesetId::ThreeMensMorris);
+    if let VariantState::Morris(state) = &mut morris.variant {
+        state
+            .positions
+            .push((Board::new(4, 4), Role::Mark(CellState::X)));
+    }
+
+    let mut quantum = fresh(RulesetId::Quantum);
+    if let VariantState::Quantum(state) = &mut quantum.variant {
+        state.subscripts.pop();
+    }
+
+    for position in [ultimate, notakto, morris, quantum] {
+        assert_eq!(
+            analyze(&position),
+            Err(GameError::InvalidConfig),
+            "{:?}",
+            position.ruleset
+        );
+    }
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use common::{cell, perft_bitboard, perft_game_state, place};
+use shared::bitboard::BitPosition;
+use shared::{BoardConfig, CellState, GameState, Move, Outcome, Role, RulesetId};
+
+/// Plays every line `depth` plies deep on both representations, checking that they
+/// agree on the moves available and on how each move ends the game.
+fn walk_in_step(state: &GameState, position: &mut BitPosition, depth: u32) {
+    let mut empty: Vec<usize> = state
+        .board
+        .empty_cells()
+        .filter_map(|cell| position.index(cell))
+        .collect();
+    empty.sort_unstable();
+    assert_eq!(position.empty_cells().collect::<Vec<_>>(), empty);
+    if depth == 0 {
+        return;
+    }
+
+    for mv in state.legal_moves() {
+        let Move::Place(cell) = mv else {
+            panic!("unexpected move {mv:?}");
+        };
+        let index = position.index(cell).unwrap();
+        let mut child = state.clone();
+        child.play(mv).unwrap();
+        position.play(index);
+
+        let outcome = child
+            .game_over
+            .then(|| child.winner.map_or(Outcome::Draw, Outcome::Win));
+        assert_eq!(position.outcome(index), outcome, "{:?}", child.board);
+        assert_same_game(&position.to_game_state("test".into()).unwrap(), &child);
+        if outcome.is_none() {
+            walk_in_step(&child, position, depth - 1);
+        }
+        position.undo(index);
+    }
+}
+
+/// Checks that `rebuilt` stands where `played` does, result included.
+fn assert_same_game(rebuilt: &GameState, played: &GameState) {
+    assert_eq!(rebuilt.board, played.board);
+    assert_eq!(
+        rebuilt.current_player, played.current_player,
+        "{:?}",
+        played.board
+    );
+    assert_eq!(rebuilt.game_over, played.game_over, "{:?}", played.board);
+    assert_eq!(rebuilt.winner, played.winner, "{:?}", played.board);
+    assert_eq!(
+        rebuilt.winning_lines, played.winning_lines,
+        "{:?}",
+        played.board
+    );
+    assert_eq!(rebuilt.rankings, played.rankings, "{:?}", played.board);
+}
+
+fn positions(ruleset: RulesetId, config: BoardConfig) -> (GameState, BitPosition) {
+    let state = GameState::with_config("test".into(), ruleset, config).unwrap();
+    let position = BitPosition::try_from(&state).unwrap();
+    (state, position)
+}
+
+#[test]
+fn perft_matches_game_state() {
+    let (state, mut position) = positions(RulesetId::Classic, BoardConfig::new(5, 5, 4).unwrap());
+    assert_eq!(
+        perft_game_state(&state, 3),
+        perft_bitboard(&mut position, 3)
+    );
+
+    let (_, mut position) = positions(RulesetId::Classic, BoardConfig::default());
+    // Every finished game of tic-tac-toe.
+    assert_eq!(perft_bitboard(&mut position, 9), 255_168);
+}
+
+#[test]
+fn outcomes_match_game_state() {
+    for ruleset in [RulesetId::Classic, RulesetId::Misere] {
+        let (state, mut position) = positions(ruleset, BoardConfig::default());
+        walk_in_step(&state, &mut position, 6);
+
+        let (state, mut position) = positions(ruleset, BoardConfig::new(4, 4, 3).unwrap());
+        walk_in_step(&state, &mut position, 4);
+    }
+}
+
+#[test]
+fn rebuilt_games_match_played_ones() {
+    let won = [
+        place(0, 0),
+        place(1, 0),
+        place(0, 1),
+        place(1, 1),
+        place(0, 2),
+    ];
+    let drawn = [
+        place(0, 0),
+        place(0, 1),
+        place(0, 2),
+        place(1, 1),
+        place(1, 0),
+        place(1, 2),
+        place(2, 1),
+        place(2, 0),
+        place(2, 2),
+    ];
+    let running = [place(1, 1), place(0, 0), place(2, 2)];
+
+    for ruleset in [RulesetId::Classic, RulesetId::Misere] {
+        for moves in [&won[..], &drawn, &running] {
+            let (mut state, mut position) = positions(ruleset, BoardConfig::default());
+            for mv in moves {
+                let Move::Place(cell) = *mv else {
+                    unreachable!();
+                };
+                state.play(*mv).unwrap();
+                position.play(position.index(cell).unwrap());
+            }
+            assert_same_game(&position.to_game_state("test".into()).unwrap(), &state);
+        }
+    }
+}
+
+#[test]
+fn the_first_line_decides_when_both_sides_have_one() {
+    let (_, mut position) = positions(RulesetId::Classic, BoardConfig::default());
+    // X completes the top row, then O the middle one.
+    for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)] {
+        position.play(position.index(cell(row, col)).unwrap());
+    }
+
+    let state = position.to_game_state("test".into()).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(state.current_player, Role::Mark(CellState::X));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 0), cell(0, 1), cell(0, 2)]]
+    );
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{cell, place};
+use shared::{Board, BoardConfig, CellState, GameError, GameState, Role, RulesetId};
+
+#[test]
+fn lines_are_found_through_any_of_their_cells() {
+    let lines = [
+        (0..5).map(|col| cell(14, 10 + col)).collect::<Vec<_>>(),
+        (0..5).map(|row| cell(row, 0)).collect(),
+        (0..5).map(|step| cell(10 + step, 10 + step)).collect(),
+        (0..5).map(|step| cell(step, 14 - step)).collect(),
+    ];
+
+    for line in lines {
+        let mut board = Board::new(15, 15);
+        for stone in &line[..4] {
+            board.set(*stone, CellState::X).unwrap();
+        }
+        assert!(line.iter().all(|stone| !board.has_line_through(*stone, 5)));
+
+        board.set(line[4], CellState::X).unwrap();
+        for stone in &line {
+            assert!(board.has_line_through(*stone, 5), "{line:?}");
+        }
+        assert!(!board.has_line_through(cell(7, 7), 5));
+        assert_eq!(board.runs(5), vec![line.clone()]);
+    }
+}
+
+#[test]
+fn gomoku_is_won_by_five_in_a_row() {
+    let mut state = GameState::with_config(
+        "test".to_string(),
+        RulesetId::Classic,
+        BoardConfig::gomoku(),
+    )
+    .unwrap();
+    for col in 3..7 {
+        state.commit(place(7, col)).unwrap();
+        state.commit(place(0, col)).unwrap();
+    }
+    assert!(!state.game_over);
+
+    state.commit(place(7, 7)).unwrap();
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![(3..8).map(|col| cell(7, col)).collect::<Vec<_>>()]
+    );
+}
+
+#[test]
+fn a_stone_in_the_way_breaks_the_line() {
+    let mut board = Board::new(15, 15);
+    for col in [0, 1, 2, 4, 5] {
+        board.set(cell(0, col), CellState::X).unwrap();
+    }
+    board.set(cell(0, 3), CellState::O).unwrap();
+    assert!((0..6).all(|col| !board.has_line_through(cell(0, col), 5)));
+    assert!(board.runs(5).is_empty());
+}
+
+#[test]
+fn board_sizes_and_run_lengths_are_checked() {
+    let config = BoardConfig::new(4, 6, 4).unwrap();
+    let state = GameState::with_config("test".to_string(), RulesetId::Classic, config).unwrap();
+    assert_eq!((state.board.rows(), state.board.cols()), (4, 6));
+
+    for (rows, cols, win_length) in [(3, 3, 4), (3, 3, 0), (0, 3, 3), (20, 20, 5)] {
+        assert_eq!(
+            BoardConfig::new(rows, cols, win_length),
+            Err(GameError::InvalidConfig),
+            "{rows}x{cols}:{win_length}"
+        );
+    }
+}
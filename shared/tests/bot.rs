@@ -0,0 +1,86 @@
+mod common;
+
+use common::{parse, place};
+use shared::bot::{choose_move, Difficulty};
+use shared::{BoardConfig, GameState, Move, RulesetId};
+
+const SEEDS: std::ops::Range<u64> = 0..16;
+
+/// Checks that every seed of `difficulty` picks `expected` in `notation`.
+fn assert_plays(difficulty: Difficulty, notation: &str, expected: Move) {
+    let state = parse(notation);
+    for seed in SEEDS {
+        assert_eq!(
+            choose_move(&state, difficulty, seed),
+            Some(expected),
+            "{difficulty:?} with seed {seed} in {notation}"
+        );
+    }
+}
+
+#[test]
+fn bots_take_an_immediate_win() {
+    for difficulty in [Difficulty::Heuristic, Difficulty::Perfect] {
+        assert_plays(difficulty, "3x3:3 classic xx1/2o/1o1 x 5", place(0, 2));
+        // Winning beats blocking.
+        assert_plays(difficulty, "3x3:3 classic xx1/oo1/3 x 5", place(0, 2));
+    }
+    assert_plays(
+        Difficulty::Heuristic,
+        "6x7:4 gravity 7/7/7/7/7/xxx1o1o x 6",
+        Move::Drop(3),
+    );
+}
+
+#[test]
+fn bots_block_an_immediate_loss() {
+    for difficulty in [Difficulty::Heuristic, Difficulty::Perfect] {
+        assert_plays(difficulty, "3x3:3 classic xx1/1o1/3 o 4", place(0, 2));
+    }
+    assert_plays(
+        Difficulty::Heuristic,
+        "6x7:4 gravity 7/7/7/7/7/xxx1o1o o 6",
+        Move::Drop(3),
+    );
+}
+
+#[test]
+fn the_heuristic_bot_finishes_an_open_four_in_gomoku() {
+    let mut state = GameState::with_config(
+        "test".to_string(),
+        RulesetId::Classic,
+        BoardConfig::gomoku(),
+    )
+    .unwrap();
+    for col in 3..7 {
+        state.commit(place(7, col)).unwrap();
+        state.commit(place(0, col * 2)).unwrap();
+    }
+
+    for seed in SEEDS {
+        let mv = choose_move(&state, Difficulty::Heuristic, seed).unwrap();
+        assert!(
+            [place(7, 2), place(7, 7)].contains(&mv),
+            "seed {seed}: {mv:?}"
+        );
+    }
+}
+
+#[test]
+fn bots_only_play_legal_moves_and_stop_when_the_game_is_over() {
+    let state = parse("3x3:3 classic xo1/1x1/o2 x 5");
+    for seed in SEEDS {
+        let mv = choose_move(&state, Difficulty::Random, seed).unwrap();
+        assert!(state.legal_moves().contains(&mv), "seed {seed}: {mv:?}");
+    }
+
+    let finished = parse("3x3:3 classic xxx/oo1/3 o 6");
+    for difficulty in [
+        Difficulty::Random,
+        Difficulty::Heuristic,
+        Difficulty::Imperfect,
+        Difficulty::Perfect,
+    ] {
+        assert_eq!(choose_move(&finished, difficulty, 0), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Helpers shared by the integration tests and benchmarks; each crate uses only
+//! some of them.
+#![allow(dead_code)]
+
+use shared::bitboard::BitPosition;
+use shared::{Coordinate, GameState, Move, RulesetId};
+
+pub fn cell(row: usize, col: usize) -> Coordinate {
+    Coordinate::new(row, col).unwrap()
+}
+
+pub fn place(row: usize, col: usize) -> Move {
+    Move::Place(cell(row, col))
+}
+
+/// A game set up from a position in [`shared::notation`].
+pub fn parse(notation: &str) -> GameState {
+    GameState::from_notation("test".to_string(), notation).unwrap()
+}
+
+/// An empty game of `ruleset` on its default board.
+pub fn fresh(ruleset: RulesetId) -> GameState {
+    let config = ruleset.ruleset().default_config();
+    GameState::with_config("test".to_string(), ruleset, config).unwrap()
+}
+
+/// Counts the positions reachable in `depth` plies, stopping at finished games.
+pub fn perft_game_state(state: &GameState, depth: u32) -> u64 {
+    if depth == 0 || state.game_over {
+        return 1;
+    }
+
+    state
+        .legal_moves()
+        .into_iter()
+        .map(|mv| {
+            let mut child = state.clone();
+            child.play(mv).unwrap();
+            perft_game_state(&child, depth - 1)
+        })
+        .sum()
+}
+
+/// [`perft_game_state`] on a bitboard, which is left as it was found.
+pub fn perft_bitboard(position: &mut BitPosition, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves: Vec<usize> = position.empty_cells().collect();
+    moves
+        .into_iter()
+        .map(|index| {
+            position.play(index);
+            let nodes = match position.outcome(index) {
+                Some(_) => 1,
+                None => perft_bitboard(position, depth - 1),
+            };
+            position.undo(index);
+            nodes
+        })
+        .sum()
+}
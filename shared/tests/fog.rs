@@ -0,0 +1,116 @@
+mod common;
+
+use common::{cell, parse, place};
+use shared::{CellState, FogState, GameError, GameState, Role, VariantState};
+use uuid::Uuid;
+
+/// A set-up fog game with X and O seated, and the ids of their players.
+fn seated(notation: &str) -> (GameState, Uuid, Uuid) {
+    let mut state = parse(notation);
+    let x = state.add_player("x".to_string()).unwrap().id;
+    let o = state.add_player("o".to_string()).unwrap().id;
+    (state, x, o)
+}
+
+#[test]
+fn the_starting_position_only_shows_the_viewers_marks() {
+    let (state, x, o) = seated("4x4:3 fog 4/1o2/2x1/4 x 3");
+
+    let views = [
+        (Some(x), "4x4:3 fog 4/4/2x1/4 x 3"),
+        (Some(o), "4x4:3 fog 4/1o2/4/4 x 3"),
+        (None, "4x4:3 fog 4/4/4/4 x 3"),
+    ];
+    for (viewer, setup) in views {
+        let view = state.view_for(viewer);
+        assert_eq!(view.setup.as_deref(), Some(setup));
+        assert_eq!(view.to_notation().unwrap(), setup);
+    }
+}
+
+#[test]
+fn players_see_their_own_marks_and_spectators_neither() {
+    let (mut state, x, o) = seated("4x4:3 fog 4/4/4/4 x 1");
+    state.make_move(place(0, 0), x).unwrap();
+    state.make_move(place(1, 1), o).unwrap();
+
+    let view = state.view_for(Some(x));
+    assert_eq!(view.board.get(cell(0, 0)), Some(CellState::X));
+    assert_eq!(view.board.get(cell(1, 1)), Some(CellState::Empty));
+
+    let view = state.view_for(Some(o));
+    assert_eq!(view.board.get(cell(0, 0)), Some(CellState::Empty));
+    assert_eq!(view.board.get(cell(1, 1)), Some(CellState::O));
+
+    let view = state.view_for(None);
+    assert_eq!(view.board.get(cell(0, 0)), Some(CellState::Empty));
+    assert_eq!(view.board.get(cell(1, 1)), Some(CellState::Empty));
+}
+
+#[test]
+fn views_leave_out_the_move_list() {
+    let (mut state, x, o) = seated("4x4:3 fog 4/4/4/4 x 1");
+    state.make_move(place(0, 0), x).unwrap();
+    state.make_move(place(1, 1), o).unwrap();
+    state.undo().unwrap();
+    assert_eq!(state.history.len(), 1);
+    assert_eq!(state.undone.len(), 1);
+
+    for viewer in [Some(x), Some(o), None] {
+        let view = state.view_for(viewer);
+        assert!(view.history.is_empty());
+        assert!(view.undone.is_empty());
+    }
+}
+
+#[test]
+fn playing_onto_a_hidden_mark_reveals_it_and_passes_the_turn() {
+    let (mut state, x, o) = seated("4x4:3 fog 4/4/4/4 x 1");
+    state.make_move(place(0, 0), x).unwrap();
+
+    // O cannot tell a1 is taken, so it is offered like any empty cell.
+    assert!(state.legal_moves().contains(&place(0, 0)));
+    state.make_move(place(0, 0), o).unwrap();
+
+    assert_eq!(state.board.get(cell(0, 0)), Some(CellState::X));
+    assert_eq!(state.current_player, Role::Mark(CellState::X));
+    assert_eq!(
+        state.variant,
+        VariantState::Fog(FogState {
+            revealed: vec![cell(0, 0)]
+        })
+    );
+    for viewer in [Some(o), None] {
+        assert_eq!(
+            state.view_for(viewer).board.get(cell(0, 0)),
+            Some(CellState::X)
+        );
+    }
+
+    // Once revealed, the cell is taken for everyone.
+    state.make_move(place(2, 2), x).unwrap();
+    assert!(!state.legal_moves().contains(&place(0, 0)));
+    assert_eq!(
+        state.make_move(place(0, 0), o),
+        Err(GameError::CellOccupied)
+    );
+}
+
+#[test]
+fn the_whole_game_is_shown_once_it_ends() {
+    let (mut state, x, o) = seated("4x4:3 fog 4/4/4/4 x 1");
+    for (mv, player) in [
+        (place(0, 0), x),
+        (place(3, 0), o),
+        (place(0, 1), x),
+        (place(3, 1), o),
+        (place(0, 2), x),
+    ] {
+        state.make_move(mv, player).unwrap();
+    }
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+
+    let view = state.view_for(None);
+    assert_eq!(view.board, state.board);
+    assert_eq!(view.history.len(), 5);
+}
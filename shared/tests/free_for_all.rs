@@ -0,0 +1,84 @@
+mod common;
+
+use common::{cell, fresh, parse, place};
+use shared::{CellState, GameError, GameState, Role, RulesetId};
+use uuid::Uuid;
+
+const X: Role = Role::Mark(CellState::X);
+const O: Role = Role::Mark(CellState::O);
+const TRIANGLE: Role = Role::Mark(CellState::Triangle);
+
+/// A three-seat game with every seat taken, and the players' ids in seat order.
+fn seated() -> (GameState, [Uuid; 3]) {
+    let mut state = fresh(RulesetId::FreeForAll);
+    let ids = ["x", "o", "triangle"].map(|name| state.add_player(name.to_string()).unwrap().id);
+    (state, ids)
+}
+
+#[test]
+fn turns_go_round_every_seat() {
+    let (mut state, [x, o, triangle]) = seated();
+    for (mv, player) in [(place(0, 0), x), (place(0, 1), o), (place(0, 2), triangle)] {
+        state.make_move(mv, player).unwrap();
+    }
+
+    assert_eq!(state.board.get(cell(0, 2)), Some(CellState::Triangle));
+    assert_eq!(state.current_player, X);
+    assert_eq!(state.make_move(place(1, 1), o), Err(GameError::NotYourTurn));
+}
+
+#[test]
+fn eliminated_seats_are_skipped_and_the_last_one_standing_wins() {
+    let (mut state, [x, o, triangle]) = seated();
+    state.eliminate(o).unwrap();
+    assert_eq!(state.current_player, X);
+
+    state.make_move(place(0, 0), x).unwrap();
+    assert_eq!(state.current_player, TRIANGLE);
+    state.make_move(place(1, 1), triangle).unwrap();
+
+    // With X gone too, Triangle is the only seat left.
+    state.eliminate(x).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(TRIANGLE));
+    assert_eq!(state.rankings, vec![vec![TRIANGLE], vec![X], vec![O]]);
+}
+
+#[test]
+fn a_full_board_ranks_seats_by_their_longest_run() {
+    let mut state = parse("3x3:3 free-for-all txt/xoo/o1x t 9 xot");
+    state.commit(place(2, 1)).unwrap();
+
+    assert!(state.game_over);
+    assert_eq!(state.winner, None);
+    assert_eq!(state.rankings, vec![vec![X, O], vec![TRIANGLE]]);
+}
+
+#[test]
+fn a_seat_left_before_the_game_fills_goes_to_the_next_player() {
+    let mut state = fresh(RulesetId::FreeForAll);
+    let first = state.add_player("first".to_string()).unwrap();
+    let second = state.add_player("second".to_string()).unwrap();
+
+    state.remove_player(first.id).unwrap();
+    assert!(!state.is_full);
+    assert_eq!(state.players.len(), 1);
+    assert_eq!(state.players[0].id, second.id);
+    assert_eq!(
+        state.remove_player(first.id),
+        Err(GameError::PlayerNotFound)
+    );
+
+    // The newcomer takes the free seat; nobody else moves.
+    let third = state.add_player("third".to_string()).unwrap();
+    assert_eq!(third.role, Role::Mark(CellState::X));
+    let fourth = state.add_player("fourth".to_string()).unwrap();
+    assert_eq!(fourth.role, Role::Mark(CellState::Triangle));
+    assert!(state.is_full);
+
+    // Once everyone is seated, leaving is a forfeit.
+    assert_eq!(
+        state.remove_player(second.id),
+        Err(GameError::GameInProgress)
+    );
+}
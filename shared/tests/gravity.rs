@@ -0,0 +1,59 @@
+mod common;
+
+use common::{cell, fresh, place};
+use shared::{CellState, GameError, Move, Role, RulesetId};
+
+#[test]
+fn pieces_fall_to_the_lowest_empty_cell() {
+    let mut state = fresh(RulesetId::Gravity);
+    for _ in 0..3 {
+        state.commit(Move::Drop(3)).unwrap();
+    }
+
+    assert_eq!(state.board.get(cell(5, 3)), Some(CellState::X));
+    assert_eq!(state.board.get(cell(4, 3)), Some(CellState::O));
+    assert_eq!(state.board.get(cell(3, 3)), Some(CellState::X));
+    assert_eq!(state.board.get(cell(2, 3)), Some(CellState::Empty));
+}
+
+#[test]
+fn a_full_column_is_refused_with_a_typed_error() {
+    let mut state = fresh(RulesetId::Gravity);
+    let x = state.add_player("x".to_string()).unwrap().id;
+    let o = state.add_player("o".to_string()).unwrap().id;
+    for player in [x, o, x, o, x, o] {
+        state.make_move(Move::Drop(0), player).unwrap();
+    }
+    let board = state.board.clone();
+
+    assert_eq!(
+        state.make_move(Move::Drop(0), x),
+        Err(GameError::ColumnFull)
+    );
+    assert_eq!(state.board, board);
+    assert_eq!(state.current_player, Role::Mark(CellState::X));
+    assert!(!state.legal_moves().contains(&Move::Drop(0)));
+
+    assert_eq!(
+        state.make_move(Move::Drop(7), x),
+        Err(GameError::OutOfBounds)
+    );
+    assert_eq!(state.make_move(place(0, 1), x), Err(GameError::InvalidMove));
+    state.make_move(Move::Drop(1), x).unwrap();
+}
+
+#[test]
+fn four_stacked_pieces_win() {
+    let mut state = fresh(RulesetId::Gravity);
+    for col in [0, 1, 0, 1, 0, 1] {
+        state.commit(Move::Drop(col)).unwrap();
+    }
+    assert!(!state.game_over);
+
+    state.commit(Move::Drop(0)).unwrap();
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![(2..6).map(|row| cell(row, 0)).collect::<Vec<_>>()]
+    );
+}
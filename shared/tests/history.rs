@@ -0,0 +1,84 @@
+mod common;
+
+use common::{cell, parse, place};
+use shared::{CellState, GameError, GameState, Role};
+
+fn snapshot(state: &GameState) -> String {
+    serde_json::to_string(state).unwrap()
+}
+
+#[test]
+fn undo_and_redo_walk_the_history() {
+    let mut state = GameState::new("test".to_string());
+    state.commit(place(1, 1)).unwrap();
+    state.commit(place(0, 0)).unwrap();
+
+    state.undo().unwrap();
+    assert_eq!(state.board.get(cell(0, 0)), Some(CellState::Empty));
+    assert_eq!(state.board.get(cell(1, 1)), Some(CellState::X));
+    assert_eq!(state.current_player, Role::Mark(CellState::O));
+    assert_eq!(state.history.len(), 1);
+    assert_eq!(state.undone.len(), 1);
+
+    state.redo().unwrap();
+    assert_eq!(state.board.get(cell(0, 0)), Some(CellState::O));
+    assert_eq!(state.current_player, Role::Mark(CellState::X));
+    assert!(state.undone.is_empty());
+    assert_eq!(state.redo(), Err(GameError::NothingToRedo));
+
+    // A new move discards whatever was taken back.
+    state.undo().unwrap();
+    state.commit(place(2, 2)).unwrap();
+    assert_eq!(state.redo(), Err(GameError::NothingToRedo));
+}
+
+#[test]
+fn undo_stops_at_the_setup_position() {
+    let setup = "3x3:3 classic x1o/1x1/2o x 5";
+    let start = parse(setup);
+    let mut state = start.clone();
+    state.commit(place(1, 0)).unwrap();
+    state.commit(place(1, 2)).unwrap();
+
+    state.undo().unwrap();
+    state.undo().unwrap();
+    assert_eq!(state.board, start.board);
+    assert_eq!(state.current_player, start.current_player);
+    assert_eq!(state.to_notation().unwrap(), setup);
+    assert_eq!(state.undo(), Err(GameError::NothingToUndo));
+}
+
+#[test]
+fn redo_replays_a_game_ending_move() {
+    let mut state = parse("3x3:3 classic xx1/2o/1o1 x 5");
+    state.commit(place(0, 2)).unwrap();
+    let finished = snapshot(&state);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+
+    state.undo().unwrap();
+    assert!(!state.game_over);
+    assert_eq!(state.winner, None);
+    assert!(state.winning_lines.is_empty());
+
+    state.redo().unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert!(!state.winning_lines.is_empty());
+    assert_eq!(state.history.len(), 1);
+    assert_eq!(snapshot(&state), finished);
+}
+
+#[test]
+fn a_history_that_does_not_replay_leaves_the_game_alone() {
+    let mut state = GameState::new("test".to_string());
+    state.commit(place(1, 1)).unwrap();
+    state.commit(place(0, 0)).unwrap();
+    state.commit(place(2, 2)).unwrap();
+
+    // The second move now lands on X's first mark.
+    state.history[1].mv = place(1, 1);
+    let before = snapshot(&state);
+
+    assert_eq!(state.undo(), Err(GameError::CellOccupied));
+    assert_eq!(snapshot(&state), before);
+}
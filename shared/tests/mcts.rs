@@ -0,0 +1,50 @@
+mod common;
+
+use common::{fresh, parse};
+use shared::mcts::{GreedyRollout, Mcts, MctsConfig};
+use shared::{BoardConfig, GameState, RulesetId};
+
+fn config(seed: u64) -> MctsConfig {
+    MctsConfig {
+        iterations: Some(500),
+        seed,
+        ..MctsConfig::default()
+    }
+}
+
+fn positions() -> Vec<GameState> {
+    vec![
+        GameState::new("test".to_string()),
+        parse("3x3:3 classic x2/1o1/3 x 3"),
+        fresh(RulesetId::Gravity),
+        GameState::with_config(
+            "test".to_string(),
+            RulesetId::Classic,
+            BoardConfig::new(5, 5, 4).unwrap(),
+        )
+        .unwrap(),
+    ]
+}
+
+#[test]
+fn same_seed_gives_the_same_search() {
+    for state in positions() {
+        let first = Mcts::new(config(7)).search(&state);
+        let second = Mcts::new(config(7)).search(&state);
+        assert_eq!(first, second);
+        assert_eq!(first.iterations, 500);
+
+        let first = Mcts::with_policy(config(7), GreedyRollout).search(&state);
+        let second = Mcts::with_policy(config(7), GreedyRollout).search(&state);
+        assert_eq!(first, second);
+    }
+}
+
+#[test]
+fn the_seed_drives_the_search() {
+    let state = GameState::new("test".to_string());
+    let searches: Vec<_> = (0..4)
+        .map(|seed| Mcts::new(config(seed)).search(&state).stats)
+        .collect();
+    assert!(searches.windows(2).any(|pair| pair[0] != pair[1]));
+}
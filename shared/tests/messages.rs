@@ -0,0 +1,24 @@
+mod common;
+
+use common::cell;
+use shared::{ClientMessage, Coordinate, GameError, RawCoordinate};
+
+#[test]
+fn moves_off_every_board_parse_and_are_refused_as_out_of_bounds() {
+    let message = r#"{"MakeMove":{"cell":{"row":25,"col":0}}}"#;
+    let ClientMessage::MakeMove { cell, .. } = serde_json::from_str(message).unwrap() else {
+        panic!("{message} is not a move");
+    };
+    assert_eq!(cell, RawCoordinate { row: 25, col: 0 });
+    assert_eq!(Coordinate::try_from(cell), Err(GameError::OutOfBounds));
+
+    // Positions keep refusing such cells outright.
+    assert!(serde_json::from_str::<Coordinate>(r#"{"row":25,"col":0}"#).is_err());
+}
+
+#[test]
+fn cells_on_the_board_convert_back() {
+    let raw = RawCoordinate::from(cell(2, 1));
+    assert_eq!(raw, RawCoordinate { row: 2, col: 1 });
+    assert_eq!(Coordinate::try_from(raw), Ok(cell(2, 1)));
+}
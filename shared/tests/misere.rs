@@ -0,0 +1,62 @@
+mod common;
+
+use common::{cell, fresh, parse, place};
+use shared::{CellState, GameError, Role, RulesetId};
+
+#[test]
+fn completing_a_line_in_misere_loses() {
+    let mut state = fresh(RulesetId::Misere);
+    for mv in [place(0, 0), place(1, 1), place(0, 1), place(2, 2)] {
+        state.commit(mv).unwrap();
+    }
+
+    state.commit(place(0, 2)).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::O)));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 0), cell(0, 1), cell(0, 2)]]
+    );
+    assert_eq!(
+        state.rankings,
+        vec![
+            vec![Role::Mark(CellState::O)],
+            vec![Role::Mark(CellState::X)]
+        ]
+    );
+}
+
+#[test]
+fn a_misere_board_filled_without_a_line_is_drawn() {
+    let mut state = parse("3x3:3 misere xox/xoo/ox1 x 9");
+    state.commit(place(2, 2)).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, None);
+}
+
+#[test]
+fn completing_the_last_live_notakto_board_loses() {
+    let mut state = fresh(RulesetId::Notakto);
+    state.commit(place(0, 0)).unwrap();
+    state.commit(place(1, 1)).unwrap();
+    state.commit(place(2, 2)).unwrap();
+
+    // The first board is dead, but two are still live.
+    assert!(!state.game_over);
+    assert_eq!(state.commit(place(0, 1)), Err(GameError::BoardDead));
+    assert!(!state.legal_moves().contains(&place(0, 1)));
+
+    let mut state = parse("3x6:3 notakto xxx3/6/6 o 4");
+    state.commit(place(0, 3)).unwrap();
+    state.commit(place(0, 4)).unwrap();
+    assert!(!state.game_over);
+
+    // O plays the line on the only live board, so X wins.
+    state.commit(place(0, 5)).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 3), cell(0, 4), cell(0, 5)]]
+    );
+}
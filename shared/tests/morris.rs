@@ -0,0 +1,43 @@
+mod common;
+
+use common::{cell, parse, place};
+use shared::{GameError, Move};
+
+fn step(from: (usize, usize), to: (usize, usize)) -> Move {
+    Move::Step(cell(from.0, from.1), cell(to.0, to.1))
+}
+
+#[test]
+fn pieces_only_step_to_adjacent_points_once_placed() {
+    let mut state = parse("3x3:3 morris xxo/o1o/1x1 x 7");
+    assert_eq!(state.commit(place(1, 1)), Err(GameError::InvalidMove));
+    assert_eq!(
+        state.commit(step((0, 0), (2, 0))),
+        Err(GameError::NotAdjacent)
+    );
+    assert_eq!(
+        state.commit(step((1, 0), (2, 0))),
+        Err(GameError::NotYourPiece)
+    );
+    state.commit(step((0, 1), (1, 1))).unwrap();
+}
+
+#[test]
+fn a_position_reached_for_the_third_time_is_a_draw() {
+    let mut state = parse("3x3:3 morris xxo/o1o/1x1 x 7");
+    let shuffle = [
+        step((2, 1), (2, 0)),
+        step((1, 2), (2, 2)),
+        step((2, 0), (2, 1)),
+        step((2, 2), (1, 2)),
+    ];
+
+    // The position after the first move comes round again on the fifth and ninth.
+    for mv in shuffle.iter().cycle().take(8) {
+        state.commit(*mv).unwrap();
+        assert!(!state.game_over);
+    }
+    state.commit(shuffle[0]).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, None);
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{cell, parse};
+use shared::notation::{cell_name, parse_cell};
+use shared::{CellState, GameError, GameState, Move, Role};
+
+#[test]
+fn positions_round_trip() {
+    let positions = [
+        "3x3:3 classic 3/3/3 x 1",
+        "3x3:3 classic x1o/1x1/2o x 5",
+        "15x15:5 classic 15/15/15/15/15/15/15/7x7/15/15/15/15/15/15/15 o 2",
+        "3x3:3 misere 1x1/3/3 o 2",
+        "6x7:4 gravity 7/7/7/7/7/xo5 x 3",
+        "3x9:3 notakto xxx6/9/9 x 4",
+        "9x9:3 ultimate 9/9/9/9/9/9/9/9/9 x 1 -",
+        "9x9:3 ultimate 9/4x4/9/9/9/9/9/9/9 o 2 b2",
+        "16x4:4 qubic x3/4/4/4/4/4/4/4/4/4/4/4/4/4/4/4 o 2",
+        "6x6:5 order-chaos 6/2o3/6/3x2/6/6 order 3",
+        "3x3:3 numerical n5n2n8/3/3 even 4",
+        "3x3:3 morris xxo/o1o/1x1 x 7",
+        "4x4:3 fog 4/1o2/2x1/4 x 3",
+        "6x6:3 free-for-all 6/1x4/2t3/3s2/6/6 o 4 xots",
+    ];
+
+    for notation in positions {
+        let state = GameState::from_notation("test".to_string(), notation)
+            .unwrap_or_else(|error| panic!("{notation}: {error}"));
+        assert_eq!(state.to_notation().unwrap(), notation);
+    }
+}
+
+#[test]
+fn played_games_round_trip() {
+    let mut state = GameState::new("test".to_string());
+    for mv in [cell(1, 1), cell(0, 0), cell(2, 2)] {
+        state.commit(Move::Place(mv)).unwrap();
+    }
+
+    let notation = state.to_notation().unwrap();
+    assert_eq!(notation, "3x3:3 classic o2/1x1/2x o 4");
+
+    let parsed = parse(&notation);
+    assert_eq!(parsed.board, state.board);
+    assert_eq!(parsed.current_player, state.current_player);
+    assert_eq!(parsed.move_number(), state.move_number());
+}
+
+#[test]
+fn set_up_games_continue_and_undo_to_their_start() {
+    let mut state = parse("3x3:3 classic 3/1x1/3 o 2");
+    state.commit(Move::Place(cell(0, 0))).unwrap();
+    assert_eq!(state.to_notation().unwrap(), "3x3:3 classic o2/1x1/3 x 3");
+
+    state.undo().unwrap();
+    assert_eq!(state.to_notation().unwrap(), "3x3:3 classic 3/1x1/3 o 2");
+}
+
+#[test]
+fn decided_positions_are_finished() {
+    let state = parse("3x3:3 classic xxx/oo1/3 o 6");
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 0), cell(0, 1), cell(0, 2)]]
+    );
+
+    let state = parse("3x3:3 classic xox/xoo/oxx x 10");
+    assert!(state.game_over);
+    assert_eq!(state.winner, None);
+}
+
+#[test]
+fn a_full_order_chaos_board_with_a_line_goes_to_order() {
+    let state = parse("6x6:5 order-chaos xxooxx/ooxxoo/xxooxx/ooxxoo/xxooxx/oooooo order 37");
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Order));
+    assert_eq!(
+        state.winning_lines,
+        vec![(0..6).map(|col| cell(5, col)).collect::<Vec<_>>()]
+    );
+
+    let state = parse("6x6:5 order-chaos xxooxx/ooxxoo/xxooxx/ooxxoo/xxooxx/ooxxoo order 37");
+    assert_eq!(state.winner, Some(Role::Chaos));
+    assert!(state.winning_lines.is_empty());
+}
+
+#[test]
+fn malformed_positions_are_rejected() {
+    let malformed = [
+        "",
+        "3x3:3 classic 3/3/3 x",
+        "3x3:3 classic 3/3/3 x 1 extra",
+        "3x3:3  classic 3/3/3 x 1",
+        "3x3:3 classic 3/3/3 x 0",
+        "3x3:3 classic 3/3/3 x 01",
+        "3x3 classic 3/3/3 x 1",
+        "3x3:3 tictactoe 3/3/3 x 1",
+        "3x3:3 classic 3/3 x 1",
+        "3x3:3 classic 3/3/3/3 x 1",
+        "3x3:3 classic 4/3/3 x 1",
+        "3x3:3 classic 2/3/3 x 1",
+        "3x3:3 classic 12/3/3 x 1",
+        "3x3:3 classic 03/3/3 x 1",
+        "3x3:3 classic X2/3/3 o 2",
+        "3x3:3 classic 3/3/3 order 1",
+        "3x3:3 numerical n0n2/3/3 odd 3",
+        "9x9:3 ultimate 9/9/9/9/9/9/9/9/9 x 1",
+        "9x9:3 ultimate 9/9/9/9/9/9/9/9/9 x 1 d1",
+        "6x6:3 free-for-all 6/6/6/6/6/6 x 1",
+    ];
+
+    for notation in malformed {
+        assert_eq!(
+            GameState::from_notation("test".to_string(), notation).unwrap_err(),
+            GameError::InvalidNotation,
+            "{notation:?}"
+        );
+    }
+}
+
+#[test]
+fn impossible_positions_are_rejected() {
+    let impossible = [
+        "4x4:5 classic 4/4/4/4 x 1",
+        "3x3:3 order-chaos 3/3/3 order 1",
+        "3x3:3 classic t2/3/3 x 2",
+        "3x3:3 notakto o2/3/3 x 2",
+        "6x7:4 gravity x6/7/7/7/7/7 o 2",
+        "3x3:3 numerical n1n1n2/3/3 odd 4",
+        "6x6:3 free-for-all 6/6/6/6/6/6 x 1 xx",
+        "3x3:3 morris xxx/x2/3 o 5",
+        "3x3:3 quantum 3/3/3 x 1",
+    ];
+
+    for notation in impossible {
+        assert_eq!(
+            GameState::from_notation("test".to_string(), notation).unwrap_err(),
+            GameError::InvalidConfig,
+            "{notation:?}"
+        );
+    }
+}
+
+#[test]
+fn cell_names_round_trip() {
+    assert_eq!(cell_name(cell(0, 0)), "a1");
+    assert_eq!(cell_name(cell(1, 1)), "b2");
+    assert_eq!(cell_name(cell(18, 18)), "s19");
+
+    for name in ["a1", "b2", "c3", "o15", "s19"] {
+        assert_eq!(cell_name(parse_cell(name).unwrap()), name);
+    }
+    for name in ["", "a", "1a", "a0", "a01", "B2", "t1", "a20"] {
+        assert!(parse_cell(name).is_err(), "{name:?}");
+    }
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::{cell, fresh};
+use shared::{CellState, GameError, Move, NumericalState, Role, RulesetId, VariantState};
+
+fn number(row: usize, col: usize, number: u8) -> Move {
+    Move::PlaceSymbol(cell(row, col), CellState::Number(number))
+}
+
+fn inventories(variant: &VariantState) -> &NumericalState {
+    match variant {
+        VariantState::Numerical(state) => state,
+        other => panic!("not a numerical game: {other:?}"),
+    }
+}
+
+#[test]
+fn each_side_plays_its_own_numbers_once() {
+    let mut state = fresh(RulesetId::Numerical);
+    assert_eq!(state.current_player, Role::Odd);
+    assert_eq!(state.legal_moves().len(), 9 * 5);
+
+    state.commit(number(1, 1, 5)).unwrap();
+    let numerical = inventories(&state.variant);
+    assert_eq!(numerical.remaining(Role::Odd), [1, 3, 7, 9]);
+    assert_eq!(numerical.remaining(Role::Even), [2, 4, 6, 8]);
+    assert_eq!(state.legal_moves().len(), 8 * 4);
+
+    assert_eq!(
+        state.commit(number(0, 0, 3)),
+        Err(GameError::NumberUnavailable)
+    );
+    state.commit(number(0, 0, 4)).unwrap();
+    assert_eq!(
+        state.commit(number(0, 1, 5)),
+        Err(GameError::NumberUnavailable)
+    );
+    assert_eq!(
+        state.commit(Move::PlaceSymbol(cell(0, 1), CellState::X)),
+        Err(GameError::InvalidMove)
+    );
+}
+
+#[test]
+fn a_line_summing_to_fifteen_wins_for_whoever_completes_it() {
+    let mut state = fresh(RulesetId::Numerical);
+    // A full line that misses the target does nothing.
+    for mv in [number(1, 0, 9), number(1, 1, 8), number(1, 2, 1)] {
+        state.commit(mv).unwrap();
+    }
+    assert!(!state.game_over);
+
+    for mv in [number(0, 1, 2), number(0, 0, 7)] {
+        state.commit(mv).unwrap();
+    }
+    assert!(!state.game_over);
+
+    // Even completes 7 + 2 + 6 with an odd number already in the line.
+    state.commit(number(0, 2, 6)).unwrap();
+    assert_eq!(state.winner, Some(Role::Even));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 0), cell(0, 1), cell(0, 2)]]
+    );
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{cell, fresh, parse, place};
+use shared::{CellState, GameError, Move, Role, RulesetId};
+
+#[test]
+fn both_sides_choose_which_mark_to_place() {
+    let mut state = fresh(RulesetId::OrderChaos);
+    let order = state.add_player("order".to_string()).unwrap();
+    let chaos = state.add_player("chaos".to_string()).unwrap();
+    assert_eq!((order.role, chaos.role), (Role::Order, Role::Chaos));
+    assert_eq!(state.legal_moves().len(), 72);
+
+    state
+        .make_move(Move::PlaceSymbol(cell(0, 0), CellState::O), order.id)
+        .unwrap();
+    state
+        .make_move(Move::PlaceSymbol(cell(0, 1), CellState::O), chaos.id)
+        .unwrap();
+    assert_eq!(state.board.get(cell(0, 0)), Some(CellState::O));
+    assert_eq!(state.board.get(cell(0, 1)), Some(CellState::O));
+
+    for mv in [
+        place(1, 1),
+        Move::PlaceSymbol(cell(1, 1), CellState::Triangle),
+    ] {
+        assert_eq!(state.make_move(mv, order.id), Err(GameError::InvalidMove));
+    }
+}
+
+#[test]
+fn five_like_marks_win_for_order_whoever_places_them() {
+    let mut state = parse("6x6:5 order-chaos oooo2/6/6/6/6/6 chaos 5");
+    state
+        .commit(Move::PlaceSymbol(cell(0, 4), CellState::O))
+        .unwrap();
+
+    assert_eq!(state.winner, Some(Role::Order));
+    assert_eq!(
+        state.winning_lines,
+        vec![(0..5).map(|col| cell(0, col)).collect::<Vec<_>>()]
+    );
+}
+
+#[test]
+fn a_full_board_without_a_line_goes_to_chaos() {
+    let mut state = parse("6x6:5 order-chaos xxooxx/ooxxoo/xxooxx/ooxxoo/xxooxx/ooxxo1 chaos 36");
+    assert!(!state.game_over);
+
+    state
+        .commit(Move::PlaceSymbol(cell(5, 5), CellState::O))
+        .unwrap();
+    assert_eq!(state.winner, Some(Role::Chaos));
+    assert!(state.winning_lines.is_empty());
+    assert_eq!(state.rankings, vec![vec![Role::Chaos], vec![Role::Order]]);
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{cell, fresh};
+use shared::{
+    CellState, GameError, GameState, Move, QuantumState, Role, RulesetId, SpookyMark, VariantState,
+};
+
+const X: CellState = CellState::X;
+const O: CellState = CellState::O;
+
+fn quantum_state(state: &GameState) -> &QuantumState {
+    match &state.variant {
+        VariantState::Quantum(quantum) => quantum,
+        other => panic!("not a quantum game: {other:?}"),
+    }
+}
+
+#[test]
+fn closing_a_cycle_waits_for_the_opponent_to_collapse_it() {
+    let mut state = fresh(RulesetId::Quantum);
+    state.play(Move::Spooky(cell(0, 0), cell(1, 1))).unwrap();
+    state.play(Move::Spooky(cell(1, 1), cell(2, 2))).unwrap();
+    state.play(Move::Spooky(cell(2, 2), cell(0, 0))).unwrap();
+
+    let pending = quantum_state(&state).pending_collapse.unwrap();
+    assert_eq!(pending.turn, 3);
+    assert_eq!(state.current_player, Role::Mark(O));
+    assert_eq!(
+        state.legal_moves(),
+        vec![Move::Collapse(cell(2, 2)), Move::Collapse(cell(0, 0))]
+    );
+    assert_eq!(
+        state.play(Move::Spooky(cell(0, 1), cell(0, 2))),
+        Err(GameError::CollapsePending)
+    );
+}
+
+#[test]
+fn collapse_propagates_through_the_cycle() {
+    let mut state = fresh(RulesetId::Quantum);
+    state.play(Move::Spooky(cell(0, 0), cell(1, 1))).unwrap();
+    state.play(Move::Spooky(cell(1, 1), cell(2, 2))).unwrap();
+    state.play(Move::Spooky(cell(2, 2), cell(0, 0))).unwrap();
+    state.play(Move::Collapse(cell(0, 0))).unwrap();
+
+    // X3 takes a1, which pushes X1 to b2 and O2 on to c3.
+    assert_eq!(state.board.get(cell(0, 0)), Some(X));
+    assert_eq!(state.board.get(cell(1, 1)), Some(X));
+    assert_eq!(state.board.get(cell(2, 2)), Some(O));
+
+    let quantum = quantum_state(&state);
+    assert!(quantum.spooky_marks.is_empty());
+    assert_eq!(quantum.pending_collapse, None);
+    assert_eq!(quantum.subscripts[0], Some(3));
+    assert_eq!(quantum.subscripts[4], Some(1));
+    assert_eq!(quantum.subscripts[8], Some(2));
+}
+
+#[test]
+fn the_collapsing_player_moves_next() {
+    let mut state = fresh(RulesetId::Quantum);
+    state.play(Move::Spooky(cell(0, 0), cell(1, 1))).unwrap();
+    state.play(Move::Spooky(cell(1, 1), cell(2, 2))).unwrap();
+    state.play(Move::Spooky(cell(2, 2), cell(0, 0))).unwrap();
+    state.play(Move::Collapse(cell(2, 2))).unwrap();
+
+    assert_eq!(state.current_player, Role::Mark(O));
+    state.play(Move::Spooky(cell(0, 1), cell(0, 2))).unwrap();
+    assert_eq!(state.current_player, Role::Mark(X));
+}
+
+#[test]
+fn simultaneous_lines_give_the_later_one_half_a_point() {
+    let mut state = fresh(RulesetId::Quantum);
+    for (target, mark, turn) in [
+        ((0, 0), X, 1),
+        ((1, 0), O, 2),
+        ((0, 1), X, 3),
+        ((1, 1), O, 4),
+    ] {
+        let target = cell(target.0, target.1);
+        state.board.set(target, mark).unwrap();
+        if let VariantState::Quantum(quantum) = &mut state.variant {
+            quantum.subscripts[target.row() * 3 + target.col()] = Some(turn);
+        }
+    }
+    let x5 = SpookyMark {
+        player: X,
+        turn: 5,
+        cells: [cell(0, 2), cell(1, 2)],
+    };
+    let o6 = SpookyMark {
+        player: O,
+        turn: 6,
+        cells: [cell(1, 2), cell(0, 2)],
+    };
+    if let VariantState::Quantum(quantum) = &mut state.variant {
+        quantum.spooky_marks = vec![x5, o6];
+        quantum.pending_collapse = Some(o6);
+        quantum.turn = 7;
+    }
+
+    // O6 collapsing into c2 forces X5 into c1, completing the top two rows at once.
+    state.play(Move::Collapse(cell(1, 2))).unwrap();
+
+    assert_eq!(quantum_state(&state).half_points, [2, 1]);
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(X)));
+}
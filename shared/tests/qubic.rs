@@ -0,0 +1,82 @@
+mod common;
+
+use common::fresh;
+use shared::qubic::{cell, lines, SIZE};
+use shared::{CellState, GameState, Move, Role, RulesetId};
+use std::collections::HashSet;
+
+fn play(state: &mut GameState, cells: &[(usize, usize, usize)]) {
+    for &(layer, row, col) in cells {
+        state
+            .play(Move::Place(cell(layer, row, col).unwrap()))
+            .unwrap();
+    }
+}
+
+#[test]
+fn all_76_lines_are_found() {
+    let lines = lines();
+    assert_eq!(lines.len(), 76);
+
+    let distinct: HashSet<Vec<usize>> = lines
+        .iter()
+        .map(|line| {
+            let mut cells = line.to_vec();
+            cells.sort_unstable();
+            cells
+        })
+        .collect();
+    assert_eq!(distinct.len(), 76);
+    assert!(lines
+        .iter()
+        .flatten()
+        .all(|index| *index < SIZE * SIZE * SIZE));
+}
+
+#[test]
+fn space_diagonal_wins_across_layers() {
+    let mut state = fresh(RulesetId::Qubic);
+    play(
+        &mut state,
+        &[
+            (0, 0, 0),
+            (0, 0, 1),
+            (1, 1, 1),
+            (0, 0, 2),
+            (2, 2, 2),
+            (1, 0, 3),
+        ],
+    );
+    assert!(!state.game_over);
+
+    play(&mut state, &[(3, 3, 3)]);
+    assert!(state.game_over);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![(0..SIZE)
+            .map(|layer| cell(layer, layer, layer).unwrap())
+            .collect::<Vec<_>>()]
+    );
+}
+
+#[test]
+fn pillar_wins_through_every_layer() {
+    let mut state = fresh(RulesetId::Qubic);
+    play(
+        &mut state,
+        &[
+            (0, 0, 0),
+            (0, 1, 2),
+            (0, 0, 1),
+            (1, 1, 2),
+            (3, 3, 3),
+            (2, 1, 2),
+            (3, 0, 0),
+        ],
+    );
+    assert!(!state.game_over);
+
+    play(&mut state, &[(3, 1, 2)]);
+    assert_eq!(state.winner, Some(Role::Mark(CellState::O)));
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{fresh, parse, place};
+use shared::record::GameRecord;
+use shared::{BoardConfig, CellState, GameError, GameState, Move, Role, RulesetId};
+
+/// X takes the left column while O plays along the top.
+fn finished_game() -> GameState {
+    let mut state = GameState::new("Friday night".to_string());
+    state.add_player("Alice".to_string()).unwrap();
+    state.add_player("Bob".to_string()).unwrap();
+    for mv in [
+        place(0, 0),
+        place(0, 1),
+        place(1, 0),
+        place(0, 2),
+        place(2, 0),
+    ] {
+        state.commit(mv).unwrap();
+    }
+    state
+}
+
+#[test]
+fn records_round_trip() {
+    let state = finished_game();
+    let record = GameRecord::from_game(&state).unwrap();
+    assert_eq!(record.tag("event"), Some("Friday night"));
+    assert_eq!(record.tag("X"), Some("Alice"));
+    assert_eq!(record.tag("O"), Some("Bob"));
+    assert_eq!(record.tag("Result"), Some("x"));
+
+    let text = record.to_string();
+    assert!(text.ends_with("\na1 b1 a2 c1 a3 x\n"), "{text}");
+    assert_eq!(GameRecord::parse(&text).unwrap(), record);
+
+    let replayed = record.replay("copy".to_string()).unwrap();
+    assert_eq!(replayed.board, state.board);
+    assert_eq!(replayed.winner, Some(Role::Mark(CellState::X)));
+}
+
+#[test]
+fn everything_but_moves_is_skipped() {
+    let text = "[Ruleset classic]\n\
+                [Board \"3x3:3\"]\n\
+                % an escaped line\n\
+                \n\
+                1. A1 b1!? $4 {a comment\nover two lines} 2. a2 (2. b2 c2 (3. c3)) c1?!\n\
+                3... a3 ; the end\n\
+                x\n";
+    let record = GameRecord::parse(text).unwrap();
+    assert_eq!(record.tag("Ruleset"), Some("classic"));
+    assert_eq!(
+        record.moves,
+        vec![
+            place(0, 0),
+            place(0, 1),
+            place(1, 0),
+            place(0, 2),
+            place(2, 0)
+        ]
+    );
+}
+
+#[test]
+fn moves_in_column_n_are_not_results() {
+    let mut state = GameState::with_config(
+        "gomoku".to_string(),
+        RulesetId::Classic,
+        BoardConfig::gomoku(),
+    )
+    .unwrap();
+    for mv in [
+        place(7, 7),
+        place(4, 13),
+        place(8, 7),
+        place(5, 13),
+        place(6, 6),
+    ] {
+        state.commit(mv).unwrap();
+    }
+
+    let record = GameRecord::from_game(&state).unwrap();
+    let text = record.to_string();
+    assert!(text.ends_with("\nh8 n5 h9 n6 g7 *\n"), "{text}");
+    assert_eq!(GameRecord::parse(&text).unwrap(), record);
+    assert_eq!(
+        record.replay("gomoku".to_string()).unwrap().board,
+        state.board
+    );
+
+    // Only the last word can be a result, and never a cell.
+    let moves = GameRecord::parse("h8 n5 h9").unwrap().moves;
+    assert_eq!(moves, vec![place(7, 7), place(4, 13), place(8, 7)]);
+    let moves = GameRecord::parse("h8 n5").unwrap().moves;
+    assert_eq!(moves, vec![place(7, 7), place(4, 13)]);
+}
+
+#[test]
+fn gravity_moves_are_the_cells_pieces_land_in() {
+    let mut state = fresh(RulesetId::Gravity);
+    for col in [3, 3, 4] {
+        state.commit(Move::Drop(col)).unwrap();
+    }
+
+    let record = GameRecord::from_game(&state).unwrap();
+    assert_eq!(record.moves, vec![place(5, 3), place(4, 3), place(5, 4)]);
+
+    let replayed = GameRecord::parse(&record.to_string())
+        .unwrap()
+        .replay("drop".to_string())
+        .unwrap();
+    assert_eq!(replayed.board, state.board);
+    assert!(replayed
+        .history
+        .iter()
+        .all(|record| matches!(record.mv, Move::Drop(_))));
+
+    // A piece cannot hang in mid-air.
+    let floating = "[Ruleset gravity]\n[Board 6x7:4]\n\nd6 d4 *\n";
+    assert_eq!(
+        GameRecord::parse(floating)
+            .unwrap()
+            .replay("drop".to_string())
+            .unwrap_err(),
+        GameError::InvalidMove
+    );
+}
+
+#[test]
+fn set_up_games_record_their_start() {
+    let setup = "3x3:3 classic 3/1x1/3 o 2";
+    let mut state = parse(setup);
+    state.commit(place(0, 0)).unwrap();
+
+    let record = GameRecord::from_game(&state).unwrap();
+    assert_eq!(record.tag("Setup"), Some(setup));
+    assert_eq!(record.moves, vec![place(0, 0)]);
+
+    let replayed = GameRecord::parse(&record.to_string())
+        .unwrap()
+        .replay("setup".to_string())
+        .unwrap();
+    assert_eq!(replayed.board, state.board);
+    assert_eq!(replayed.setup.as_deref(), Some(setup));
+    assert_eq!(replayed.move_number(), 3);
+}
+
+#[test]
+fn unreadable_moves_are_rejected() {
+    for text in ["a1 zz9 *", "a1~ *", "b2=q *"] {
+        assert_eq!(
+            GameRecord::parse(text).unwrap_err(),
+            GameError::InvalidRecord,
+            "{text:?}"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{parse, place};
+use shared::solver::{self, Solver, Value};
+use shared::symmetry::Symmetry;
+use shared::{GameError, GameState};
+
+#[test]
+fn empty_classic_board_is_a_draw() {
+    let solution = solver::solve(&GameState::new("test".to_string())).unwrap();
+    assert_eq!(solution.value, Value::Draw);
+    assert_eq!(solution.best_moves.len(), 9);
+}
+
+#[test]
+fn distances_survive_the_transposition_table() {
+    // X can win at once on c1, or fork with a2 and win two plies later.
+    let before = parse("3x3:3 classic xx1/2o/1o1 x 5");
+    let after = parse("3x3:3 classic xx1/x1o/1o1 o 6");
+
+    let mut solver = Solver::new();
+    assert_eq!(solver.solve(&before).unwrap().value, Value::Win(1));
+    assert_eq!(solver.solve(&before).unwrap().best_moves, vec![place(0, 2)]);
+
+    let values = solver.analyze(&before).unwrap();
+    assert!(values.contains(&(place(0, 2), Value::Win(1))));
+    assert!(values.contains(&(place(1, 0), Value::Win(3))));
+
+    // The fork's position is now cached at a deeper ply; its own distance is unchanged.
+    assert_eq!(solver.solve(&after).unwrap().value, Value::Loss(2));
+    assert_eq!(solver::solve(&after).unwrap().value, Value::Loss(2));
+}
+
+#[test]
+fn misere_values() {
+    assert_eq!(
+        solver::solve(&parse("3x3:3 misere 3/3/3 x 1"))
+            .unwrap()
+            .value,
+        Value::Draw
+    );
+    // The last empty cell completes X's own row.
+    assert_eq!(
+        solver::solve(&parse("3x3:3 misere xx1/oox/oxo x 9"))
+            .unwrap()
+            .value,
+        Value::Loss(1)
+    );
+}
+
+#[test]
+fn notakto_values() {
+    let solution = solver::solve(&parse("3x3:3 notakto 3/3/3 x 1")).unwrap();
+    assert!(matches!(solution.value, Value::Win(_)), "{solution:?}");
+    assert!(solution.best_moves.contains(&place(1, 1)));
+
+    // Either move left completes a line on the only board.
+    assert_eq!(
+        solver::solve(&parse("3x3:3 notakto xx1/x1x/1xx o 7"))
+            .unwrap()
+            .value,
+        Value::Loss(1)
+    );
+}
+
+#[test]
+fn values_do_not_depend_on_symmetry() {
+    for notation in [
+        "3x3:3 classic x2/3/3 o 2",
+        "3x3:3 classic x2/1o1/2x o 4",
+        "3x3:3 classic xx1/2o/1o1 x 5",
+        "3x3:3 misere 1x1/3/o2 x 3",
+        "4x4:4 classic xo2/1xo1/2x1/o3 x 6",
+    ] {
+        let state = parse(notation);
+        let value = solver::solve(&state).unwrap().value;
+        for symmetry in Symmetry::ALL {
+            let mut image = state.clone();
+            image.board = state.board.transformed(symmetry).unwrap();
+            assert_eq!(
+                solver::solve(&image).unwrap().value,
+                value,
+                "{notation} under {symmetry:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn searches_give_up_when_out_of_budget() {
+    let state = parse("3x3:3 classic 3/3/3 x 1");
+    assert_eq!(
+        Solver::with_budget(100).solve(&state),
+        Err(GameError::TooComplex)
+    );
+    assert_eq!(
+        Solver::with_budget(100).analyze(&state),
+        Err(GameError::TooComplex)
+    );
+}
+
+#[test]
+fn only_games_that_must_end_are_searched() {
+    assert!(solver::is_solvable(&parse("3x3:3 classic xx1/2o/1o1 x 5")));
+    // Morris pieces can shuffle back and forth for ever.
+    assert!(!solver::is_solvable(&parse("3x3:3 morris xxo/o1o/1x1 x 7")));
+}
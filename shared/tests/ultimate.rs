@@ -0,0 +1,69 @@
+mod common;
+
+use common::{cell, fresh, parse, place};
+use shared::{
+    ultimate, CellState, GameError, Move, Role, RulesetId, SubBoard, UltimateState, VariantState,
+};
+
+fn ultimate_state(variant: &VariantState) -> &UltimateState {
+    match variant {
+        VariantState::Ultimate(state) => state,
+        other => panic!("not an ultimate game: {other:?}"),
+    }
+}
+
+#[test]
+fn a_move_sends_the_opponent_to_the_matching_sub_board() {
+    let mut state = fresh(RulesetId::Ultimate);
+    assert_eq!(state.legal_moves().len(), 81);
+
+    // The top-right cell of the top-middle sub-board...
+    state.commit(place(0, 5)).unwrap();
+    // ...sends O to the top-right sub-board.
+    assert_eq!(
+        ultimate_state(&state.variant).active_board,
+        Some(cell(0, 2))
+    );
+    let moves = state.legal_moves();
+    assert_eq!(moves.len(), 9);
+    assert!(moves.iter().all(|mv| matches!(
+        mv,
+        Move::Place(target) if ultimate::sub_board_of(*target) == cell(0, 2)
+    )));
+
+    assert_eq!(state.commit(place(4, 4)), Err(GameError::WrongSubBoard));
+    state.commit(place(2, 6)).unwrap();
+    assert_eq!(
+        ultimate_state(&state.variant).active_board,
+        Some(cell(2, 0))
+    );
+}
+
+#[test]
+fn being_sent_to_a_finished_sub_board_frees_the_choice() {
+    let mut state = parse("9x9:3 ultimate xxx6/3oo4/9/9/9/9/9/9/9 o 6 -");
+    assert_eq!(
+        ultimate_state(&state.variant).sub_board(cell(0, 0)),
+        SubBoard::Won(CellState::X)
+    );
+
+    // The top-left cell of the centre sub-board points at the won top-left one.
+    state.commit(place(3, 3)).unwrap();
+    assert_eq!(ultimate_state(&state.variant).active_board, None);
+    let moves = state.legal_moves();
+    assert_eq!(moves.len(), 69);
+    assert!(!moves.contains(&place(1, 1)));
+}
+
+#[test]
+fn three_sub_boards_in_a_row_win() {
+    let mut state = parse("9x9:3 ultimate xxxxxxxx1/9/9/oo1oo1oo1/oo7/9/9/9/9 x 17 -");
+    assert!(!state.game_over);
+
+    state.commit(place(0, 8)).unwrap();
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 0), cell(0, 1), cell(0, 2)]]
+    );
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{cell, fresh, parse, place};
+use shared::{BoardConfig, CellState, Coordinate, GameState, Move, Role, RulesetId};
+
+/// The reported lines in a fixed order, since rulesets may find them in any.
+fn sorted(lines: &[Vec<Coordinate>]) -> Vec<Vec<Coordinate>> {
+    let mut lines = lines.to_vec();
+    lines.sort_by_key(|line| {
+        line.iter()
+            .map(|cell| (cell.row(), cell.col()))
+            .collect::<Vec<_>>()
+    });
+    lines
+}
+
+#[test]
+fn a_move_completing_two_lines_reports_both() {
+    let mut state = parse("3x3:3 classic xx1/oox/o1x x 8");
+    state.commit(place(0, 2)).unwrap();
+
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        sorted(&state.winning_lines),
+        vec![
+            vec![cell(0, 0), cell(0, 1), cell(0, 2)],
+            vec![cell(0, 2), cell(1, 2), cell(2, 2)],
+        ]
+    );
+}
+
+#[test]
+fn a_gomoku_overline_is_reported_end_to_end() {
+    let mut state = GameState::with_config(
+        "test".to_string(),
+        RulesetId::Classic,
+        BoardConfig::gomoku(),
+    )
+    .unwrap();
+    for col in [0, 1, 2, 4, 5] {
+        state.commit(place(7, col)).unwrap();
+        state.commit(place(0, col * 2)).unwrap();
+    }
+
+    state.commit(place(7, 3)).unwrap();
+    assert_eq!(
+        state.winning_lines,
+        vec![(0..6).map(|col| cell(7, col)).collect::<Vec<_>>()]
+    );
+}
+
+#[test]
+fn a_morris_line_formed_by_a_step_is_reported() {
+    let mut state = parse("3x3:3 morris xxo/o1o/1x1 x 7");
+    state.commit(Move::Step(cell(0, 0), cell(1, 1))).unwrap();
+
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 1), cell(1, 1), cell(2, 1)]]
+    );
+}
+
+#[test]
+fn a_free_for_all_line_belongs_to_its_seat() {
+    let mut state = fresh(RulesetId::FreeForAll);
+    for mv in [
+        place(0, 0),
+        place(1, 0),
+        place(2, 0),
+        place(0, 1),
+        place(1, 1),
+        place(2, 1),
+    ] {
+        state.commit(mv).unwrap();
+    }
+
+    state.commit(place(0, 2)).unwrap();
+    assert_eq!(state.winner, Some(Role::Mark(CellState::X)));
+    assert_eq!(
+        state.winning_lines,
+        vec![vec![cell(0, 0), cell(0, 1), cell(0, 2)]]
+    );
+}
+
+#[test]
+fn draws_and_undone_wins_report_no_lines() {
+    let mut state = parse("3x3:3 classic xox/xoo/ox1 x 9");
+    state.commit(place(2, 2)).unwrap();
+    assert!(state.game_over);
+    assert_eq!(state.winner, None);
+    assert!(state.winning_lines.is_empty());
+
+    let mut state = parse("3x3:3 classic xx1/oo1/3 x 5");
+    state.commit(place(0, 2)).unwrap();
+    assert!(!state.winning_lines.is_empty());
+    state.undo().unwrap();
+    assert!(!state.game_over);
+    assert!(state.winning_lines.is_empty());
+}
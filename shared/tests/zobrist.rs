@@ -0,0 +1,86 @@
+mod common;
+
+use common::{cell, fresh, parse, place};
+use shared::symmetry::Symmetry;
+use shared::{zobrist, Board, GameState, Move, RulesetId};
+
+/// The hash of `board` worked out from scratch.
+fn recomputed(board: &Board) -> u64 {
+    board
+        .coordinates()
+        .map(|cell| zobrist::cell_key(cell, board.get(cell).unwrap()))
+        .fold(0, |hash, key| hash ^ key)
+}
+
+fn assert_hash_is_current(state: &GameState) {
+    assert_eq!(state.board.zobrist(), recomputed(&state.board));
+    assert_eq!(
+        state.zobrist(),
+        recomputed(&state.board) ^ zobrist::role_key(state.current_player)
+    );
+}
+
+#[test]
+fn the_incremental_hash_matches_a_full_recompute() {
+    let mut state = fresh(RulesetId::Classic);
+    assert_hash_is_current(&state);
+    for mv in [place(1, 1), place(0, 0), place(2, 2), place(0, 2)] {
+        state.commit(mv).unwrap();
+        assert_hash_is_current(&state);
+        assert_eq!(state.history.last().unwrap().hash, state.zobrist());
+    }
+
+    // Stepping clears the cell a piece leaves.
+    let mut state = parse("3x3:3 morris xxo/o1o/1x1 x 7");
+    state.commit(Move::Step(cell(2, 1), cell(2, 0))).unwrap();
+    assert_hash_is_current(&state);
+
+    // Boards read from the wire rebuild their hash.
+    let json = serde_json::to_string(&state.board).unwrap();
+    let board: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(board.zobrist(), state.board.zobrist());
+}
+
+#[test]
+fn undoing_a_move_restores_the_hash() {
+    let mut state = fresh(RulesetId::Classic);
+    state.commit(place(1, 1)).unwrap();
+    let before = state.zobrist();
+
+    state.commit(place(0, 0)).unwrap();
+    assert_ne!(state.zobrist(), before);
+    state.undo().unwrap();
+    assert_eq!(state.zobrist(), before);
+}
+
+#[test]
+fn rotations_and_reflections_share_a_canonical_hash() {
+    let state = parse("3x3:3 classic xo1/1x1/3 o 4");
+    let (canonical, _) = state.canonical();
+
+    for symmetry in Symmetry::ALL {
+        let mut image = state.clone();
+        image.board = state.board.transformed(symmetry).unwrap();
+        assert_eq!(
+            image.canonical_hash(),
+            state.canonical_hash(),
+            "{symmetry:?}"
+        );
+        assert_eq!(image.canonical().0, canonical, "{symmetry:?}");
+
+        let (board, applied) = image.canonical();
+        assert_eq!(image.board.transformed(applied).unwrap(), board);
+    }
+
+    let other = parse("3x3:3 classic x2/1o1/3 x 3");
+    assert_ne!(other.canonical_hash(), state.canonical_hash());
+}
+
+#[test]
+fn positions_without_symmetry_hash_as_they_are() {
+    let mut state = fresh(RulesetId::Ultimate);
+    state.commit(place(0, 5)).unwrap();
+    assert!(!state.is_symmetric());
+    assert_eq!(state.canonical_hash(), state.zobrist());
+    assert_eq!(state.canonical_symmetry(), Symmetry::Identity);
+}